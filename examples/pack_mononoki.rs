@@ -15,12 +15,27 @@
 
 use std::fs::File;
 
-use font_mud::glyph_atlas::GlyphAtlas;
+use font_mud::prelude::*;
 
 fn main() {
     let ttf_src = include_bytes!("mononoki-Regular.ttf");
-    let face = ttf_parser::Face::parse(ttf_src, 0).unwrap();
-    let (glyph_atlas, _glyph_shape_errors) = GlyphAtlas::new(&face).unwrap();
+    let face = Face::parse(ttf_src, 0).unwrap();
+    let atlases = [PackOrder::Insertion, PackOrder::HeightDescending].map(|order| {
+        let (atlas, _glyph_shape_errors) = GlyphAtlas::builder()
+            .pack_order(order)
+            .build(&face)
+            .unwrap();
+        println!(
+            "{:?}: packed {}x{} atlas at {:.1}% efficiency",
+            order,
+            atlas.width,
+            atlas.height,
+            atlas.packing_efficiency() * 100.0
+        );
+        atlas
+    });
+
+    let [_, glyph_atlas] = atlases;
     let bitmap = glyph_atlas.generate_full();
     let output = File::create("mononoki.png").unwrap();
     let mut encoder = png::Encoder::new(output, bitmap.width, bitmap.height);
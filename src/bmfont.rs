@@ -0,0 +1,128 @@
+// Copyright (c) 2023 Marceline Cramer
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::{self, Write};
+
+use crate::{glyph_atlas::GlyphAtlas, metrics::MetricSource};
+
+impl GlyphAtlas {
+    /// writes an AngelCode BMFont text descriptor for this atlas.
+    ///
+    /// `face_name` goes in the `info` line and `page_file` names the atlas image.
+    /// BMFont coordinates are top-down while atlas rows are bottom-up,
+    /// so the atlas bitmap must be flipped vertically when it is saved as `page_file`.
+    pub fn write_bmfont<W: Write>(
+        &self,
+        mut writer: W,
+        face_name: &str,
+        page_file: &str,
+    ) -> io::Result<()> {
+        let px_per_em = self.px_per_em as f32;
+        let px_per_unit = self.metrics.px_per_unit(px_per_em);
+        let base = self.metrics.ascender as f32 * px_per_unit;
+        let line_height = self.metrics.line_height(px_per_em, MetricSource::Face);
+
+        writeln!(
+            writer,
+            "info face=\"{}\" size={} bold=0 italic=0 charset=\"\" unicode=1 stretchH=100 smooth=1 aa=1 padding=0,0,0,0 spacing=0,0",
+            face_name.replace('"', "'"),
+            px_per_em.round() as i32,
+        )?;
+
+        writeln!(
+            writer,
+            "common lineHeight={} base={} scaleW={} scaleH={} pages=1 packed=0",
+            line_height.round() as i32,
+            base.round() as i32,
+            self.width,
+            self.height,
+        )?;
+
+        writeln!(writer, "page id=0 file=\"{}\"", page_file)?;
+        writeln!(writer, "chars count={}", self.chars.len())?;
+
+        for (c, glyph) in self.chars.iter() {
+            let xadvance = self.advances[glyph.0 as usize] * px_per_em;
+
+            let (x, y, width, height, xoffset, yoffset) = match &self.glyphs[glyph.0 as usize] {
+                Some(info) => {
+                    let left = -info.shape.anchor.x * px_per_em;
+                    let top = -info.shape.anchor.y * px_per_em + info.size.y as f32;
+                    (
+                        info.position.x,
+                        self.height - info.position.y - info.size.y,
+                        info.size.x,
+                        info.size.y,
+                        left.round() as i32,
+                        (base - top).round() as i32,
+                    )
+                }
+                None => (0, 0, 0, 0, 0, 0),
+            };
+
+            writeln!(
+                writer,
+                "char id={} x={} y={} width={} height={} xoffset={} yoffset={} xadvance={} page=0 chnl=15",
+                *c as u32,
+                x,
+                y,
+                width,
+                height,
+                xoffset,
+                yoffset,
+                xadvance.round() as i32,
+            )?;
+        }
+
+        // BMFont kerning is keyed by character, so every char sharing a glyph gets the pair
+        let chars_by_glyph = self.chars_by_glyph();
+
+        let mut kernings = Vec::new();
+        for (left, right, amount) in self.kerning.iter() {
+            let amount = (amount * px_per_em).round() as i32;
+            if amount == 0 {
+                continue;
+            }
+
+            let (Some(firsts), Some(seconds)) =
+                (chars_by_glyph.get(left), chars_by_glyph.get(right))
+            else {
+                continue;
+            };
+
+            for first in firsts.iter() {
+                for second in seconds.iter() {
+                    kernings.push((*first, *second, amount));
+                }
+            }
+        }
+
+        if kernings.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(writer, "kernings count={}", kernings.len())?;
+
+        for (first, second, amount) in kernings {
+            writeln!(
+                writer,
+                "kerning first={} second={} amount={}",
+                first as u32, second as u32, amount
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+// Copyright (c) 2023 Marceline Cramer
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use glam::{uvec2, UVec2, Vec2};
+use msdfgen::Range;
+use rect_packer::Packer;
+use ttf_parser::{Face, GlyphId};
+
+use crate::{
+    error::{FontError, FontResult},
+    glyph_atlas::GlyphAtlas,
+    glyph_bitmap::{GenerateConfig, GlyphBitmap, GlyphShapeBuilder},
+};
+
+/// where a glyph was placed in a [DynamicAtlas].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GlyphRect {
+    /// the index of the page the glyph is on.
+    pub page: usize,
+    /// the glyph's position on the page in texels.
+    pub position: UVec2,
+    /// the glyph's size in texels.
+    pub size: UVec2,
+    /// the glyph's anchor, as in [crate::glyph_bitmap::GlyphShape::anchor].
+    pub anchor: Vec2,
+}
+
+/// a single fixed-size texture in a [DynamicAtlas].
+pub struct DynamicPage {
+    pub bitmap: GlyphBitmap,
+    packer: Packer,
+}
+
+impl DynamicPage {
+    fn new(size: u32, config: &GenerateConfig, background: u32) -> Self {
+        Self {
+            bitmap: config.blank_bitmap(size, size, background),
+            packer: Packer::new(rect_packer::Config {
+                width: size as i32,
+                height: size as i32,
+                border_padding: 0,
+                rectangle_padding: 0,
+            }),
+        }
+    }
+}
+
+/// a glyph atlas that bakes glyphs on demand, as they are first needed.
+///
+/// glyphs are packed into square pages of a fixed size. when no page has room for a new glyph,
+/// a new page is added, so existing glyphs never move. glyphs are never evicted;
+/// call [DynamicAtlas::clear] to start over when memory use matters more than rebaking.
+/// a dynamic atlas caches glyphs by id and size only, so use one per face.
+/// each page's background is [GenerateConfig::outside_texel] at the range of the glyph that opened it,
+/// which differs between sizes only for a dilated [Range::Unit].
+pub struct DynamicAtlas {
+    page_size: u32,
+    shape: GlyphShapeBuilder,
+    config: GenerateConfig,
+    pages: Vec<DynamicPage>,
+    rects: HashMap<(GlyphId, u64), Option<GlyphRect>>,
+}
+
+impl DynamicAtlas {
+    pub fn new(page_size: u32, range: Range<f64>) -> Self {
+        let shape = GlyphShapeBuilder::new(GlyphAtlas::PX_PER_EM, range);
+        Self::with_config(page_size, shape, GenerateConfig::default())
+    }
+
+    /// creates a dynamic atlas that builds each glyph's shape with `shape`, at the size it is requested at.
+    /// the builder's own pixels per em are ignored, but all of its other options, like [GlyphShapeBuilder::min_size], apply.
+    pub fn with_config(page_size: u32, shape: GlyphShapeBuilder, config: GenerateConfig) -> Self {
+        Self {
+            page_size,
+            shape,
+            config,
+            pages: vec![],
+            rects: HashMap::new(),
+        }
+    }
+
+    pub fn pages(&self) -> &[DynamicPage] {
+        &self.pages
+    }
+
+    /// returns a glyph's cached rect at the given size, without baking it.
+    /// returns `None` if the glyph isn't cached yet, or if it has no outline.
+    pub fn glyph_rect(&self, px_per_em: f64, glyph: GlyphId) -> Option<GlyphRect> {
+        self.rects
+            .get(&(glyph, px_per_em.to_bits()))
+            .copied()
+            .flatten()
+    }
+
+    /// returns a glyph's rect at the given size, baking and inserting it first if it isn't cached yet.
+    ///
+    /// glyphs without an outline, like spaces, have nothing to draw and return `Ok(None)`.
+    /// that is cached too, so laying out whitespace every frame never looks up the outline again.
+    /// returns [FontError::PackingError] if the glyph is larger than a page,
+    /// and [FontError::InvalidParameter] if the size or the atlas's [GenerateConfig] is invalid.
+    pub fn glyph_rect_or_insert(
+        &mut self,
+        face: &Face,
+        px_per_em: f64,
+        glyph: GlyphId,
+    ) -> FontResult<Option<GlyphRect>> {
+        let key = (glyph, px_per_em.to_bits());
+        if let Some(rect) = self.rects.get(&key) {
+            return Ok(*rect);
+        }
+
+        self.config.validate()?;
+        let mut builder = self.shape.clone();
+        builder.px_per_em = px_per_em;
+        let shape = match builder.build(face, glyph) {
+            Ok(shape) => shape,
+            Err(FontError::GlyphShape(_)) => {
+                self.rects.insert(key, None);
+                return Ok(None);
+            }
+            Err(err) => return Err(err),
+        };
+
+        if shape.width > self.page_size || shape.height > self.page_size {
+            return Err(FontError::PackingError(glyph));
+        }
+
+        let (width, height) = (shape.width as i32, shape.height as i32);
+        let packed = self
+            .pages
+            .iter_mut()
+            .enumerate()
+            .find_map(|(index, page)| Some((index, page.packer.pack(width, height, false)?)));
+
+        let (page, packed) = match packed {
+            Some(packed) => packed,
+            None => {
+                let background = self.config.outside_texel(shape.effective_range_px());
+                let mut page = DynamicPage::new(self.page_size, &self.config, background);
+                let packed = page
+                    .packer
+                    .pack(width, height, false)
+                    .ok_or(FontError::PackingError(glyph))?;
+                self.pages.push(page);
+                (self.pages.len() - 1, packed)
+            }
+        };
+
+        let position = uvec2(packed.x as u32, packed.y as u32);
+        let bitmap = shape.generate_with(&self.config);
+        bitmap.copy_to(&mut self.pages[page].bitmap, position.x, position.y);
+
+        let rect = GlyphRect {
+            page,
+            position,
+            size: uvec2(shape.width, shape.height),
+            anchor: shape.anchor,
+        };
+
+        self.rects.insert(key, Some(rect));
+        Ok(Some(rect))
+    }
+
+    /// the size in bytes of every page's bitmap, for budgeting texture memory.
+    pub fn texture_bytes(&self) -> usize {
+        self.pages
+            .iter()
+            .map(|page| page.bitmap.data.len() * 4)
+            .sum()
+    }
+
+    /// an estimate of the bytes of CPU memory held by the cached glyph rects.
+    pub fn metadata_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.pages.capacity() * std::mem::size_of::<DynamicPage>()
+            + self.rects.capacity() * std::mem::size_of::<((GlyphId, u64), Option<GlyphRect>)>()
+    }
+
+    /// removes every page and cached glyph.
+    pub fn clear(&mut self) {
+        self.pages.clear();
+        self.rects.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mononoki() -> Face<'static> {
+        Face::parse(include_bytes!("../examples/mononoki-Regular.ttf"), 0).unwrap()
+    }
+
+    #[test]
+    fn cache_hits() {
+        let face = mononoki();
+        let a = face.glyph_index('A').unwrap();
+        let mut atlas = DynamicAtlas::new(256, Range::Px(4.0));
+
+        let rect = atlas.glyph_rect_or_insert(&face, 32.0, a).unwrap().unwrap();
+        let before = atlas.pages()[0].bitmap.data.clone();
+        assert_eq!(
+            atlas.glyph_rect_or_insert(&face, 32.0, a).unwrap(),
+            Some(rect)
+        );
+        assert_eq!(atlas.glyph_rect(32.0, a), Some(rect));
+        assert_eq!(atlas.pages()[0].bitmap.data, before);
+        assert_eq!(atlas.glyph_rect(16.0, a), None);
+
+        let space = face.glyph_index(' ').unwrap();
+        assert_eq!(
+            atlas.glyph_rect_or_insert(&face, 32.0, space).unwrap(),
+            None
+        );
+        assert!(atlas.rects.contains_key(&(space, 32.0_f64.to_bits())));
+        assert_eq!(atlas.pages().len(), 1);
+    }
+
+    #[test]
+    fn add_pages() {
+        let face = mononoki();
+        let mut atlas = DynamicAtlas::new(64, Range::Px(4.0));
+        let rects: Vec<_> = ('A'..='Z')
+            .map(|c| {
+                let glyph = face.glyph_index(c).unwrap();
+                let rect = atlas.glyph_rect_or_insert(&face, 32.0, glyph).unwrap();
+                (glyph, rect.unwrap())
+            })
+            .collect();
+
+        assert!(atlas.pages().len() > 1);
+        assert_eq!(rects.last().unwrap().1.page, atlas.pages().len() - 1);
+        for (glyph, rect) in rects {
+            assert_eq!(atlas.glyph_rect(32.0, glyph), Some(rect));
+            assert!(rect.position.x + rect.size.x <= 64 && rect.position.y + rect.size.y <= 64);
+        }
+
+        let w = face.glyph_index('W').unwrap();
+        assert!(matches!(
+            atlas.glyph_rect_or_insert(&face, 256.0, w),
+            Err(FontError::PackingError(_))
+        ));
+    }
+}
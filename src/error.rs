@@ -29,6 +29,29 @@ pub enum FontError {
         range: Range<f64>,
     },
     PackingError(GlyphId),
+    InvalidFont(&'static str),
+    MissingGlyph(GlyphId),
+    GlyphTooLarge {
+        glyph: GlyphId,
+        width: u32,
+        height: u32,
+        slot_width: u32,
+        slot_height: u32,
+    },
+    InvalidParameter {
+        name: &'static str,
+        value: f64,
+    },
+    ChannelMismatch {
+        src: u32,
+        dst: u32,
+    },
+    OutOfBounds {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -57,6 +80,47 @@ impl fmt::Display for FontError {
             FontError::PackingError(glyph) => {
                 write!(f, "packing error for glyph: {}", glyph.0)
             }
+            FontError::InvalidFont(reason) => {
+                write!(f, "invalid font: {}", reason)
+            }
+            FontError::MissingGlyph(glyph) => {
+                write!(f, "glyph {} is not in the atlas", glyph.0)
+            }
+            FontError::GlyphTooLarge {
+                glyph,
+                width,
+                height,
+                slot_width,
+                slot_height,
+            } => {
+                write!(
+                    f,
+                    "glyph {} is {}x{}, which does not fit its {}x{} atlas slot",
+                    glyph.0, width, height, slot_width, slot_height
+                )
+            }
+            FontError::InvalidParameter { name, value } => {
+                write!(f, "invalid {}: {}", name, value)
+            }
+            FontError::ChannelMismatch { src, dst } => {
+                write!(
+                    f,
+                    "cannot copy a {}-channel bitmap into a {}-channel bitmap",
+                    src, dst
+                )
+            }
+            FontError::OutOfBounds {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                write!(
+                    f,
+                    "{}x{} rectangle at ({}, {}) is out of bounds",
+                    width, height, x, y
+                )
+            }
         }
     }
 }
@@ -0,0 +1,182 @@
+// Copyright (c) 2023 Marceline Cramer
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use msdfgen::Range;
+use ttf_parser::{Face, GlyphId};
+
+use crate::{
+    dynamic_atlas::{DynamicAtlas, GlyphRect},
+    error::{FontError, FontResult, GlyphShapeError},
+    glyph_atlas::GlyphAtlas,
+    glyph_bitmap::{GlyphShape, GlyphShapeBuilder},
+    layout::{layout_line, PlacedGlyph},
+    metrics::FontMetrics,
+};
+
+/// an owned face bundled with its metrics, a size, and a cache of baked glyphs.
+///
+/// this is a facade over the lower-level types for callers who don't want to manage them by hand.
+/// [Face] borrows its data, so the font keeps the data itself and re-parses the face when it is needed.
+/// parsing reads the headers of every table the face uses, so it is cheap but not free:
+/// batch per-character work with [Self::with_face] or [Self::glyph_rects] rather than calling
+/// [Self::glyph] or [Self::glyph_rect] in a loop.
+pub struct Font {
+    data: Arc<[u8]>,
+    index: u32,
+    metrics: FontMetrics,
+    px_per_em: f64,
+    range: Range<f64>,
+    atlas: DynamicAtlas,
+}
+
+impl Font {
+    /// the size of each page of [Self::atlas].
+    pub const PAGE_SIZE: u32 = 1024;
+
+    /// parses the face at `index` in `data`, returning [FontError::InvalidFont] if it is malformed.
+    pub fn new(
+        data: impl Into<Arc<[u8]>>,
+        index: u32,
+        px_per_em: f64,
+        range: Range<f64>,
+    ) -> FontResult<Self> {
+        let data = data.into();
+        let face = Face::parse(&data, index)
+            .map_err(|_| FontError::InvalidFont("failed to parse face"))?;
+        let metrics = FontMetrics::new(&face);
+
+        Ok(Self {
+            index,
+            metrics,
+            px_per_em,
+            range,
+            atlas: DynamicAtlas::new(Self::PAGE_SIZE, range),
+            data,
+        })
+    }
+
+    /// parses the face. this cannot fail, since the data was already parsed by [Self::new].
+    pub fn face(&self) -> Face<'_> {
+        parse_face(&self.data, self.index)
+    }
+
+    /// parses the face once and calls `f` with it, for lookups that would otherwise re-parse it every time.
+    pub fn with_face<T>(&self, f: impl FnOnce(&Face) -> T) -> T {
+        f(&self.face())
+    }
+
+    pub fn metrics(&self) -> &FontMetrics {
+        &self.metrics
+    }
+
+    pub fn px_per_em(&self) -> f64 {
+        self.px_per_em
+    }
+
+    pub fn range(&self) -> Range<f64> {
+        self.range
+    }
+
+    /// the glyph the face maps a character to, if any.
+    /// this parses the face on every call; use [Self::with_face] to look up many characters.
+    pub fn glyph(&self, c: char) -> Option<GlyphId> {
+        self.face().glyph_index(c)
+    }
+
+    /// builds the shape of a character's glyph at the font's size.
+    /// characters missing from the face use the .notdef glyph.
+    pub fn shape(&self, c: char) -> FontResult<GlyphShape> {
+        let face = self.face();
+        let glyph = face.glyph_index(c).unwrap_or(GlyphId(0));
+        GlyphShapeBuilder::new(self.px_per_em, self.range).build(&face, glyph)
+    }
+
+    /// lays out a single line of text at the font's size. see [layout_line].
+    pub fn layout(&self, text: &str) -> Vec<PlacedGlyph> {
+        layout_line(&self.face(), self.px_per_em as f32, text)
+    }
+
+    /// bakes a standalone atlas of the given characters at the font's size.
+    /// see [crate::glyph_atlas::GlyphAtlasBuilder::build_chars].
+    pub fn bake(
+        &self,
+        chars: impl IntoIterator<Item = char>,
+    ) -> FontResult<(GlyphAtlas, Vec<GlyphShapeError>)> {
+        GlyphAtlas::builder()
+            .shape(GlyphShapeBuilder::new(self.px_per_em, self.range))
+            .build_chars(&self.face(), chars)
+    }
+
+    /// returns a glyph's rect in [Self::atlas], baking it on first use.
+    /// glyphs without an outline, like spaces, return `Ok(None)`. see [DynamicAtlas::glyph_rect_or_insert].
+    pub fn glyph_rect(&mut self, glyph: GlyphId) -> FontResult<Option<GlyphRect>> {
+        let face = parse_face(&self.data, self.index);
+        self.atlas
+            .glyph_rect_or_insert(&face, self.px_per_em, glyph)
+    }
+
+    /// like [Self::glyph_rect] for each of `glyphs`, parsing the face only once.
+    pub fn glyph_rects(
+        &mut self,
+        glyphs: impl IntoIterator<Item = GlyphId>,
+    ) -> FontResult<Vec<Option<GlyphRect>>> {
+        let face = parse_face(&self.data, self.index);
+        glyphs
+            .into_iter()
+            .map(|glyph| {
+                self.atlas
+                    .glyph_rect_or_insert(&face, self.px_per_em, glyph)
+            })
+            .collect()
+    }
+
+    /// the cache of glyphs baked by [Self::glyph_rect].
+    pub fn atlas(&self) -> &DynamicAtlas {
+        &self.atlas
+    }
+}
+
+/// parses a face that [Font::new] already parsed successfully.
+/// this takes the data rather than the font so that the face doesn't borrow the whole font.
+fn parse_face(data: &[u8], index: u32) -> Face<'_> {
+    Face::parse(data, index).expect("face was parsed by Font::new")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_lookups() {
+        let data: &[u8] = include_bytes!("../examples/mononoki-Regular.ttf");
+        let mut font = Font::new(data, 0, 32.0, Range::Px(4.0)).unwrap();
+
+        let glyphs: Vec<GlyphId> = font.with_face(|face| {
+            "a b"
+                .chars()
+                .map(|c| face.glyph_index(c).unwrap())
+                .collect()
+        });
+        assert_eq!(glyphs, ['a', ' ', 'b'].map(|c| font.glyph(c).unwrap()));
+
+        let rects = font.glyph_rects(glyphs.iter().copied()).unwrap();
+        assert!(rects[0].is_some() && rects[1].is_none() && rects[2].is_some());
+        for (glyph, rect) in glyphs.into_iter().zip(rects) {
+            assert_eq!(font.glyph_rect(glyph).unwrap(), rect);
+        }
+    }
+}
@@ -14,14 +14,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
 use glam::{uvec2, UVec2, Vec2};
 use msdfgen::Range;
 use rect_packer::Packer;
-use ttf_parser::{Face, GlyphId};
+use ttf_parser::{kern, Face, GlyphId};
 
 use crate::{
     error::{FontError, FontResult, GlyphShapeError},
-    glyph_bitmap::{GlyphBitmap, GlyphShape},
+    glyph_bitmap::{GenerateConfig, GlyphBitmap, GlyphKind, GlyphShape, GlyphShapeBuilder},
+    layout::{advance_px, is_kerning_subtable},
+    metrics::FontMetrics,
 };
 
 #[derive(Copy, Clone, Debug)]
@@ -42,6 +48,236 @@ pub struct GlyphAtlas {
     pub width: u32,
     pub height: u32,
     pub glyphs: Vec<Option<GlyphInfo>>,
+    pub config: GenerateConfig,
+    pub px_per_em: f64,
+    pub range: Range<f64>,
+    pub metrics: FontMetrics,
+    /// the horizontal advance of every glyph in the face, in ems, indexed by glyph id.
+    pub advances: Vec<f32>,
+    /// the unicode characters mapped by the face to glyphs in this atlas.
+    pub chars: BTreeMap<char, GlyphId>,
+    /// horizontal kerning pairs between glyphs in this atlas from the face's `kern` table, in ems.
+    pub kerning: Vec<(GlyphId, GlyphId, f32)>,
+    /// the grid's cells, if the atlas was packed with [PackOrder::Grid].
+    pub grid: Option<GridLayout>,
+}
+
+/// the order in which glyphs are handed to the rectangle packer.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PackOrder {
+    /// packs glyphs in glyph id order.
+    /// use this when glyphs are appended over time, so that earlier placements stay stable.
+    #[default]
+    Insertion,
+
+    /// packs the tallest glyphs first.
+    /// this keeps rows of similarly-sized glyphs together and usually results in a smaller atlas.
+    /// check [GlyphAtlas::packing_efficiency] on your own fonts to compare the two orders;
+    /// the `pack_mononoki` example prints both for mononoki.
+    HeightDescending,
+
+    /// skips the rectangle packer and places glyphs in glyph id order on a regular grid.
+    ///
+    /// every cell is as large as the largest glyph, and each glyph is centered in its cell,
+    /// so a glyph's tile is found from its cell index alone; see [GlyphAtlas::grid].
+    /// this suits icon fonts whose glyphs share a size, and wastes a lot of space on fonts whose glyphs do not.
+    Grid,
+}
+
+/// the cells of an atlas packed with [PackOrder::Grid].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GridLayout {
+    /// the size of every cell in texels.
+    pub cell: UVec2,
+    /// the number of cells in each row of the atlas.
+    pub columns: u32,
+    /// the glyph in each cell, by cell index.
+    ///
+    /// cells follow the order glyphs were baked in, which is ascending glyph id among the baked glyphs only,
+    /// so a glyph's cell index is not its [GlyphId] unless every glyph of the face was baked.
+    pub glyphs: Vec<GlyphId>,
+}
+
+impl GridLayout {
+    /// the position of a cell's bottom-left corner in texels. cell 0 is in the bottom-left of the atlas.
+    pub fn cell_origin(&self, index: u32) -> UVec2 {
+        uvec2(index % self.columns, index / self.columns) * self.cell
+    }
+
+    /// the index of the cell holding `glyph`, or `None` if it was not baked.
+    pub fn cell_index(&self, glyph: GlyphId) -> Option<u32> {
+        self.glyphs
+            .binary_search(&glyph)
+            .ok()
+            .map(|index| index as u32)
+    }
+}
+
+/// configures how a [GlyphAtlas] is built.
+#[derive(Clone, Debug)]
+pub struct GlyphAtlasBuilder {
+    shape: GlyphShapeBuilder,
+    pack_order: PackOrder,
+    config: GenerateConfig,
+}
+
+impl Default for GlyphAtlasBuilder {
+    fn default() -> Self {
+        Self {
+            shape: GlyphShapeBuilder::new(GlyphAtlas::PX_PER_EM, GlyphAtlas::RANGE)
+                .angle_threshold(GlyphAtlas::ANGLE_THRESHOLD),
+            pack_order: PackOrder::default(),
+            config: GenerateConfig::default(),
+        }
+    }
+}
+
+impl GlyphAtlasBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// sets how each glyph's shape is created, including its size and distance range.
+    pub fn shape(mut self, shape: GlyphShapeBuilder) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// sets the minimum width and height of each glyph. see [GlyphShapeBuilder::min_size].
+    pub fn min_size(mut self, min_size: u32) -> Self {
+        self.shape = self.shape.min_size(min_size);
+        self
+    }
+
+    /// sets the distance range for a target on-screen range. see [GlyphShapeBuilder::target_screen_px_range].
+    /// this also accounts for the [GenerateConfig::distance_scale] of [Self::generate_config], so set that first.
+    pub fn target_screen_px_range(
+        mut self,
+        screen_px_range: f64,
+        min_screen_px_per_em: f64,
+    ) -> FontResult<Self> {
+        let distance_scale = self.config.distance_scale as f64;
+        self.shape = self.shape.scaled_screen_px_range(
+            screen_px_range,
+            min_screen_px_per_em,
+            distance_scale,
+        )?;
+        Ok(self)
+    }
+
+    pub fn pack_order(mut self, pack_order: PackOrder) -> Self {
+        self.pack_order = pack_order;
+        self
+    }
+
+    /// sets how each glyph's bitmap is generated.
+    /// building returns [FontError::InvalidParameter] if the config is invalid; see [GenerateConfig::validate].
+    pub fn generate_config(mut self, config: GenerateConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// turns a face into a glyph atlas.
+    /// glyphs are always indexed by their original [GlyphId], regardless of pack order.
+    pub fn build(&self, face: &Face) -> FontResult<(GlyphAtlas, Vec<GlyphShapeError>)> {
+        let wanted = vec![true; face.number_of_glyphs() as usize];
+        GlyphAtlas::build(self, face, &wanted, vec![])
+    }
+
+    /// like [Self::build], but only bakes the given glyphs. duplicates are baked once.
+    /// glyph ids outside of the face are reported as glyph shape errors.
+    pub fn build_glyphs(
+        &self,
+        face: &Face,
+        glyphs: &[GlyphId],
+    ) -> FontResult<(GlyphAtlas, Vec<GlyphShapeError>)> {
+        let mut wanted = vec![false; face.number_of_glyphs() as usize];
+        let mut glyph_shape_errors = vec![];
+        for glyph in glyphs.iter() {
+            match wanted.get_mut(glyph.0 as usize) {
+                Some(wanted) => *wanted = true,
+                None => glyph_shape_errors.push(GlyphShapeError(*glyph)),
+            }
+        }
+
+        glyph_shape_errors.sort_by_key(|error| error.0);
+        glyph_shape_errors.dedup_by_key(|error| error.0);
+        GlyphAtlas::build(self, face, &wanted, glyph_shape_errors)
+    }
+
+    /// like [Self::build], but only bakes the glyphs for the given characters.
+    /// the atlas's [GlyphAtlas::chars] maps exactly the given characters that the face covers.
+    pub fn build_chars(
+        &self,
+        face: &Face,
+        chars: impl IntoIterator<Item = char>,
+    ) -> FontResult<(GlyphAtlas, Vec<GlyphShapeError>)> {
+        let chars = chars
+            .into_iter()
+            .filter_map(|c| Some((c, face.glyph_index(c)?)));
+
+        self.build_char_map(face, chars)
+    }
+
+    /// like [Self::build_chars], but takes each character's glyph from `chars` instead of the face's `cmap`.
+    ///
+    /// this is for faces made by a subsetter, which remaps glyph ids and may drop the `cmap` entirely.
+    /// pass the subsetter's mapping from the original characters to the subset's glyph ids,
+    /// and [GlyphAtlas::chars] will record it, so that code working in original characters can still find each tile.
+    /// characters mapped to glyph ids outside of the face are left out, and the glyph ids reported as glyph shape errors.
+    pub fn build_char_map(
+        &self,
+        face: &Face,
+        chars: impl IntoIterator<Item = (char, GlyphId)>,
+    ) -> FontResult<(GlyphAtlas, Vec<GlyphShapeError>)> {
+        let chars: BTreeMap<char, GlyphId> = chars.into_iter().collect();
+        let glyphs: Vec<GlyphId> = chars.values().copied().collect();
+        let (mut atlas, glyph_shape_errors) = self.build_glyphs(face, &glyphs)?;
+        atlas.chars = chars
+            .into_iter()
+            .filter(|(_, glyph)| glyph.0 < face.number_of_glyphs())
+            .collect();
+        Ok((atlas, glyph_shape_errors))
+    }
+}
+
+/// bakes exactly the glyphs of an already-shaped glyph run, such as the output of rustybuzz.
+/// the atlas is indexed by [GlyphId] as usual, with every other glyph left empty.
+pub fn bake_glyph_run(
+    face: &Face,
+    px_per_em: f64,
+    range: Range<f64>,
+    glyph_ids: &[GlyphId],
+) -> FontResult<(GlyphAtlas, Vec<GlyphShapeError>)> {
+    bake_builder(px_per_em, range).build_glyphs(face, glyph_ids)
+}
+
+/// bakes the glyphs for a range of characters, such as `' '..='~'`, or a block from [crate::unicode::block].
+/// use [GlyphAtlas::chars] to look up each character's glyph at runtime.
+pub fn bake_char_range(
+    face: &Face,
+    px_per_em: f64,
+    range: Range<f64>,
+    chars: RangeInclusive<char>,
+) -> FontResult<(GlyphAtlas, Vec<GlyphShapeError>)> {
+    bake_builder(px_per_em, range).build_chars(face, chars)
+}
+
+/// bakes the glyphs for every character in a string.
+/// use [GlyphAtlas::chars] to look up each character's glyph at runtime.
+pub fn bake_string(
+    face: &Face,
+    px_per_em: f64,
+    range: Range<f64>,
+    text: &str,
+) -> FontResult<(GlyphAtlas, Vec<GlyphShapeError>)> {
+    bake_builder(px_per_em, range).build_chars(face, text.chars())
+}
+
+fn bake_builder(px_per_em: f64, range: Range<f64>) -> GlyphAtlasBuilder {
+    let shape =
+        GlyphShapeBuilder::new(px_per_em, range).angle_threshold(GlyphAtlas::ANGLE_THRESHOLD);
+    GlyphAtlas::builder().shape(shape)
 }
 
 impl GlyphAtlas {
@@ -52,17 +288,29 @@ impl GlyphAtlas {
     /// turns a face into a glyph atlas.
     /// all fonts have some glyph shape errors for some reason, we pass those through, as we treat them as non-fatal errors.
     pub fn new(face: &Face) -> FontResult<(GlyphAtlas, Vec<GlyphShapeError>)> {
+        GlyphAtlasBuilder::new().build(face)
+    }
+
+    pub fn builder() -> GlyphAtlasBuilder {
+        GlyphAtlasBuilder::new()
+    }
+
+    fn build(
+        builder: &GlyphAtlasBuilder,
+        face: &Face,
+        wanted: &[bool],
+        mut glyph_shape_errors: Vec<GlyphShapeError>,
+    ) -> FontResult<(GlyphAtlas, Vec<GlyphShapeError>)> {
+        builder.config.validate()?;
+
         let mut glyphs = Vec::with_capacity(face.number_of_glyphs() as usize);
-        let mut glyph_shape_errors = vec![];
         for c in 0..face.number_of_glyphs() {
-            let glyph = GlyphShape::new(
-                face.units_per_em() as f64,
-                Self::PX_PER_EM,
-                Self::RANGE,
-                Self::ANGLE_THRESHOLD,
-                face,
-                GlyphId(c),
-            );
+            if !wanted[c as usize] {
+                glyphs.push(None);
+                continue;
+            }
+
+            let glyph = builder.shape.build(face, GlyphId(c));
 
             match glyph {
                 Ok(glyph) => {
@@ -80,7 +328,7 @@ impl GlyphAtlas {
             }
         }
 
-        let (atlas_size, packed) = Self::pack(&glyphs);
+        let (atlas_size, packed, grid) = Self::pack(&glyphs, builder.pack_order);
         let texture_size = atlas_size.as_vec2();
 
         let glyphs: Vec<_> = packed
@@ -133,12 +381,187 @@ impl GlyphAtlas {
                 width: atlas_size.x,
                 height: atlas_size.y,
                 glyphs,
+                config: builder.config,
+                px_per_em: builder.shape.px_per_em,
+                range: builder.shape.range,
+                metrics: FontMetrics::new(face),
+                advances: Self::advances(face),
+                chars: Self::chars(face, wanted),
+                kerning: Self::kerning(face, wanted),
+                grid,
             },
             glyph_shape_errors,
         ))
     }
 
-    fn pack(glyphs: &Vec<Option<GlyphShape>>) -> (UVec2, Vec<Option<UVec2>>) {
+    fn advances(face: &Face) -> Vec<f32> {
+        (0..face.number_of_glyphs())
+            .map(|c| advance_px(face, 1.0, GlyphId(c)))
+            .collect()
+    }
+
+    fn chars(face: &Face, wanted: &[bool]) -> BTreeMap<char, GlyphId> {
+        let mut chars = BTreeMap::new();
+        let Some(cmap) = face.tables().cmap else {
+            return chars;
+        };
+
+        for subtable in cmap.subtables.into_iter().filter(|s| s.is_unicode()) {
+            subtable.codepoints(|codepoint| {
+                let Some(c) = char::from_u32(codepoint) else {
+                    return;
+                };
+
+                let Some(glyph) = subtable.glyph_index(codepoint) else {
+                    return;
+                };
+
+                if wanted.get(glyph.0 as usize).copied().unwrap_or(false) {
+                    chars.entry(c).or_insert(glyph);
+                }
+            });
+        }
+
+        chars
+    }
+
+    fn kerning(face: &Face, wanted: &[bool]) -> Vec<(GlyphId, GlyphId, f32)> {
+        let Some(table) = face.tables().kern else {
+            return vec![];
+        };
+
+        let units_per_em = face.units_per_em() as f32;
+        let mut kerning = vec![];
+        for subtable in table.subtables.into_iter().filter(is_kerning_subtable) {
+            if let kern::Format::Format0(format) = subtable.format {
+                for pair in format.pairs {
+                    let is_wanted = |glyph: GlyphId| wanted.get(glyph.0 as usize).copied();
+                    if is_wanted(pair.left()) != Some(true) || is_wanted(pair.right()) != Some(true)
+                    {
+                        continue;
+                    }
+
+                    let amount = pair.value as f32 / units_per_em;
+                    kerning.push((pair.left(), pair.right(), amount));
+                }
+            }
+        }
+
+        kerning
+    }
+
+    /// replaces a glyph's tile in a bitmap made by [Self::generate_full] without repacking.
+    ///
+    /// the rest of the glyph's slot is cleared to [Self::outside_texel].
+    /// the glyph's vertices are left as-is, so `new_bitmap` should be framed like the original.
+    /// returns [FontError::GlyphTooLarge] if `new_bitmap` is larger than the slot,
+    /// and [FontError::ChannelMismatch] if it has a different channel count than `atlas_bitmap`.
+    pub fn rerender_glyph(
+        &self,
+        atlas_bitmap: &mut GlyphBitmap,
+        glyph: GlyphId,
+        new_bitmap: &GlyphBitmap,
+    ) -> FontResult<()> {
+        let info = self
+            .glyphs
+            .get(glyph.0 as usize)
+            .and_then(Option::as_ref)
+            .ok_or(FontError::MissingGlyph(glyph))?;
+
+        if new_bitmap.width > info.size.x || new_bitmap.height > info.size.y {
+            return Err(FontError::GlyphTooLarge {
+                glyph,
+                width: new_bitmap.width,
+                height: new_bitmap.height,
+                slot_width: info.size.x,
+                slot_height: info.size.y,
+            });
+        }
+
+        if new_bitmap.channels() != atlas_bitmap.channels() {
+            return Err(FontError::ChannelMismatch {
+                src: new_bitmap.channels(),
+                dst: atlas_bitmap.channels(),
+            });
+        }
+
+        let mut clear = GlyphBitmap::new_filled(info.size.x, info.size.y, self.outside_texel());
+        clear.channel_order = self.config.channel_order;
+        clear.kind = atlas_bitmap.kind;
+        clear.try_copy_to(atlas_bitmap, info.position.x, info.position.y)?;
+        new_bitmap.try_copy_to(atlas_bitmap, info.position.x, info.position.y)
+    }
+
+    /// groups [Self::chars] by the glyph they map to.
+    pub(crate) fn chars_by_glyph(&self) -> HashMap<GlyphId, Vec<char>> {
+        let mut chars_by_glyph: HashMap<GlyphId, Vec<char>> = HashMap::new();
+        for (c, glyph) in self.chars.iter() {
+            chars_by_glyph.entry(*glyph).or_default().push(*c);
+        }
+
+        chars_by_glyph
+    }
+
+    /// the width of the distance range in atlas texels, as encoded after [GenerateConfig::distance_scale].
+    pub fn range_px(&self) -> f64 {
+        let range_px = match self.range {
+            Range::Px(px) => px,
+            Range::Unit(units) => units * self.px_per_em / self.metrics.units_per_em as f64,
+        };
+
+        range_px / self.config.distance_scale as f64
+    }
+
+    /// the fraction of the atlas area covered by glyph rectangles, or 0 for an empty atlas.
+    pub fn packing_efficiency(&self) -> f32 {
+        if self.width == 0 || self.height == 0 {
+            return 0.0;
+        }
+
+        let used: u64 = self
+            .glyphs
+            .iter()
+            .flatten()
+            .map(|glyph| glyph.size.x as u64 * glyph.size.y as u64)
+            .sum();
+
+        used as f32 / (self.width as u64 * self.height as u64) as f32
+    }
+
+    /// the size in bytes of the bitmap made by [Self::generate_full], for budgeting texture memory.
+    pub fn texture_bytes(&self) -> usize {
+        self.width as usize * self.height as usize * 4
+    }
+
+    /// an estimate of the bytes of CPU memory held by the atlas's metadata.
+    /// this counts the glyph, advance, character, and kerning tables, but not the outlines owned by msdfgen.
+    pub fn metadata_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.glyphs.capacity() * std::mem::size_of::<Option<GlyphInfo>>()
+            + self.advances.capacity() * std::mem::size_of::<f32>()
+            + self.chars.len() * std::mem::size_of::<(char, GlyphId)>()
+            + self.kerning.capacity() * std::mem::size_of::<(GlyphId, GlyphId, f32)>()
+            + self.grid.as_ref().map_or(0, |grid| {
+                grid.glyphs.capacity() * std::mem::size_of::<GlyphId>()
+            })
+    }
+
+    fn pack(
+        glyphs: &[Option<GlyphShape>],
+        order: PackOrder,
+    ) -> (UVec2, Vec<Option<UVec2>>, Option<GridLayout>) {
+        if order == PackOrder::Grid {
+            let (size, packed, grid) = Self::pack_grid(glyphs);
+            return (size, packed, Some(grid));
+        }
+
+        let mut indices: Vec<usize> = (0..glyphs.len()).collect();
+        if order == PackOrder::HeightDescending {
+            indices.sort_by_key(|index| {
+                std::cmp::Reverse(glyphs[*index].as_ref().map(|glyph| glyph.height))
+            });
+        }
+
         let mut config = rect_packer::Config {
             width: 256,
             height: 256,
@@ -151,11 +574,10 @@ impl GlyphAtlas {
 
         let packed = loop {
             let mut out_of_room = false;
-            let mut packed = Vec::with_capacity(glyphs.len());
+            let mut packed = vec![None; glyphs.len()];
 
-            for glyph in glyphs.iter() {
-                let Some(glyph) = glyph else {
-                    packed.push(None);
+            for index in indices.iter().copied() {
+                let Some(glyph) = &glyphs[index] else {
                     continue;
                 };
 
@@ -165,7 +587,7 @@ impl GlyphAtlas {
                 };
 
                 let position = uvec2(rect.x as u32, rect.y as u32);
-                packed.push(Some(position));
+                packed[index] = Some(position);
             }
 
             if out_of_room {
@@ -182,17 +604,223 @@ impl GlyphAtlas {
             }
         };
 
-        (uvec2(config.width as u32, config.height as u32), packed)
+        (
+            uvec2(config.width as u32, config.height as u32),
+            packed,
+            None,
+        )
     }
 
+    fn pack_grid(glyphs: &[Option<GlyphShape>]) -> (UVec2, Vec<Option<UVec2>>, GridLayout) {
+        let cell = glyphs.iter().flatten().fold(UVec2::ZERO, |cell, glyph| {
+            cell.max(uvec2(glyph.width, glyph.height))
+        });
+
+        let baked: Vec<GlyphId> = glyphs
+            .iter()
+            .enumerate()
+            .filter(|(_, glyph)| glyph.is_some())
+            .map(|(index, _)| GlyphId(index as u16))
+            .collect();
+
+        let count = baked.len() as u32;
+        let columns = ((count as f64).sqrt().ceil() as u32).max(1);
+        let rows = count.div_ceil(columns);
+        let grid = GridLayout {
+            cell,
+            columns,
+            glyphs: baked,
+        };
+
+        let mut cell_index = 0;
+        let packed = glyphs
+            .iter()
+            .map(|glyph| {
+                let glyph = glyph.as_ref()?;
+                let origin = grid.cell_origin(cell_index);
+                cell_index += 1;
+                Some(origin + (cell - uvec2(glyph.width, glyph.height)) / 2)
+            })
+            .collect();
+
+        (uvec2(columns, rows) * cell, packed, grid)
+    }
+
+    /// generates every glyph into a single bitmap.
+    /// texels not covered by a glyph are set to [Self::outside_texel].
     pub fn generate_full(&self) -> GlyphBitmap {
-        let mut bitmap = GlyphBitmap::new(self.width, self.height);
+        self.generate_full_with_background(self.outside_texel())
+    }
+
+    /// the texel value for "fully outside" every glyph in this atlas. see [GenerateConfig::outside_texel].
+    pub fn outside_texel(&self) -> u32 {
+        let range_px = self.range_px() * self.config.distance_scale as f64;
+        self.config.outside_texel(range_px)
+    }
+
+    /// creates an empty bitmap the size of the atlas, with its texels set to `background`
+    /// and its channel order and kind matching [Self::config].
+    pub fn blank_bitmap(&self, background: u32) -> GlyphBitmap {
+        self.config
+            .blank_bitmap(self.width, self.height, background)
+    }
+
+    /// generates every glyph into a single bitmap, setting uncovered texels to `background`.
+    pub fn generate_full_with_background(&self, background: u32) -> GlyphBitmap {
+        let mut bitmap = self.blank_bitmap(background);
+        self.copy_glyphs_into(&mut bitmap);
+        bitmap
+    }
+
+    /// generates every glyph into an existing bitmap, such as one made with [GlyphBitmap::with_row_alignment]
+    /// so that the atlas can be uploaded without repacking its rows.
+    ///
+    /// texels outside of the glyph tiles are left as they are, and the bitmap's channel order is kept.
+    /// returns [FontError::OutOfBounds] if the bitmap is smaller than the atlas,
+    /// and [FontError::ChannelMismatch] if it has a different channel count than [Self::config]'s mode.
+    pub fn generate_full_into(&self, bitmap: &mut GlyphBitmap) -> FontResult<()> {
+        if bitmap.width < self.width || bitmap.height < self.height {
+            return Err(FontError::OutOfBounds {
+                x: 0,
+                y: 0,
+                width: self.width,
+                height: self.height,
+            });
+        }
+
+        let kind: GlyphKind = self.config.mode.into();
+        if kind.channels() != bitmap.channels() {
+            return Err(FontError::ChannelMismatch {
+                src: kind.channels(),
+                dst: bitmap.channels(),
+            });
+        }
+
+        self.copy_glyphs_into(bitmap);
+        Ok(())
+    }
 
+    /// copies every generated glyph into a bitmap that is known to fit the atlas.
+    fn copy_glyphs_into(&self, bitmap: &mut GlyphBitmap) {
         for glyph in self.glyphs.iter().flatten() {
-            let glyph_bitmap = glyph.shape.generate();
+            let glyph_bitmap = glyph.shape.generate_with(&self.config);
+            glyph_bitmap.copy_to(bitmap, glyph.position.x, glyph.position.y);
+        }
+    }
+
+    /// like [Self::generate_full_with_background], but also records how long each glyph took to generate.
+    /// this is meant for finding pathologically slow glyphs, and is kept separate so that
+    /// [Self::generate_full] pays nothing for it.
+    pub fn generate_full_timed(&self, background: u32) -> (GlyphBitmap, Vec<(GlyphId, Duration)>) {
+        let mut bitmap = self.blank_bitmap(background);
+        let mut timings = Vec::with_capacity(self.glyphs.len());
+
+        for (index, glyph) in self.glyphs.iter().enumerate() {
+            let Some(glyph) = glyph else {
+                continue;
+            };
+
+            let start = Instant::now();
+            let glyph_bitmap = glyph.shape.generate_with(&self.config);
+            timings.push((GlyphId(index as u16), start.elapsed()));
             glyph_bitmap.copy_to(&mut bitmap, glyph.position.x, glyph.position.y);
         }
 
+        (bitmap, timings)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl GlyphAtlas {
+    /// like [Self::generate_full], but generates glyphs in parallel on the current rayon pool,
+    /// which is the global pool unless called from inside [rayon::ThreadPool::install].
+    pub fn generate_full_par(&self) -> GlyphBitmap {
+        use rayon::prelude::*;
+
+        let tiles: Vec<_> = self
+            .glyphs
+            .par_iter()
+            .filter_map(Option::as_ref)
+            .map(|glyph| (glyph.position, glyph.shape.generate_pooled(&self.config)))
+            .collect();
+
+        let mut bitmap = self.blank_bitmap(self.outside_texel());
+        for (position, tile) in tiles {
+            tile.copy_to(&mut bitmap, position.x, position.y);
+        }
+
         bitmap
     }
+
+    /// like [Self::generate_full_par], but runs on a caller-provided pool
+    /// so that the host application stays in control of its threads.
+    pub fn generate_full_in(&self, pool: &rayon::ThreadPool) -> GlyphBitmap {
+        pool.install(|| self.generate_full_par())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONONOKI: &[u8] = include_bytes!("../examples/mononoki-Regular.ttf");
+
+    #[test]
+    fn grid_cells() {
+        let face = Face::parse(MONONOKI, 0).unwrap();
+        let (atlas, _) = GlyphAtlas::builder()
+            .pack_order(PackOrder::Grid)
+            .build_chars(&face, "0123456789".chars())
+            .unwrap();
+
+        let grid = atlas.grid.as_ref().unwrap();
+        assert_eq!(grid.glyphs.len(), 10);
+        assert_eq!(grid.columns, 4);
+        assert_eq!(
+            (atlas.width, atlas.height),
+            (grid.cell.x * 4, grid.cell.y * 3)
+        );
+
+        for (index, glyph) in grid.glyphs.iter().enumerate() {
+            let info = atlas.glyphs[glyph.0 as usize].as_ref().unwrap();
+            let origin = grid.cell_origin(index as u32);
+            assert_eq!(grid.cell_index(*glyph), Some(index as u32));
+            assert_eq!(origin + (grid.cell - info.size) / 2, info.position);
+        }
+    }
+
+    #[test]
+    fn empty_grid() {
+        let face = Face::parse(MONONOKI, 0).unwrap();
+        let (atlas, _) = GlyphAtlas::builder()
+            .pack_order(PackOrder::Grid)
+            .build_chars(&face, " ".chars())
+            .unwrap();
+
+        assert_eq!((atlas.width, atlas.height), (0, 0));
+        assert_eq!(atlas.packing_efficiency(), 0.0);
+        assert_eq!(atlas.grid.unwrap().glyphs, vec![]);
+    }
+
+    #[test]
+    fn generate_into_aligned_rows() {
+        let face = Face::parse(MONONOKI, 0).unwrap();
+        let (atlas, _) = GlyphAtlas::builder()
+            .build_chars(&face, "Ag".chars())
+            .unwrap();
+
+        let mut aligned = GlyphBitmap::with_row_alignment(atlas.width, atlas.height, 256);
+        atlas.generate_full_into(&mut aligned).unwrap();
+        assert_eq!(aligned.row_stride() % 256, 0);
+        assert_eq!(
+            aligned.to_rgba_bytes(),
+            atlas.generate_full().to_rgba_bytes()
+        );
+
+        let mut small = GlyphBitmap::new(atlas.width - 1, atlas.height);
+        assert!(matches!(
+            atlas.generate_full_into(&mut small),
+            Err(FontError::OutOfBounds { .. })
+        ));
+    }
 }
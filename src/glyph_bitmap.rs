@@ -14,11 +14,176 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt;
+
 use glam::Vec2;
-use msdfgen::{Bitmap, FillRule, FontExt, MsdfGeneratorConfig, Range, Rgba, Shape};
+use msdfgen::{
+    Bitmap, FillRule, FontExt, GeneratorConfig, Gray, MsdfGeneratorConfig, Range, Rgba, Shape,
+    Vector2,
+};
 use ttf_parser::{Face, GlyphId};
 
-use crate::error::{FontError, FontResult, GlyphShapeError};
+use crate::{
+    error::{FontError, FontResult, GlyphShapeError},
+    outline::OutlineRecorder,
+};
+
+/// the kind of distance field to generate.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum GlyphMode {
+    /// a multi-channel distance field with the true distance in alpha.
+    /// sample with the median of the color channels.
+    #[default]
+    Mtsdf,
+
+    /// a single-channel (Valve-style) signed distance field.
+    ///
+    /// texels store `0.5 + distance / range`, clamped to `0.0..=1.0`, where distance is in pixels and positive inside.
+    /// the glyph edge is exactly 0.5, and the field spreads `range / 2` pixels to either side of it,
+    /// so shaders using `smoothstep` around 0.5 work unmodified.
+    /// the value is replicated into every channel so that any channel may be sampled.
+    Sdf,
+}
+
+/// what the texels of a [GlyphBitmap] hold, and so how they should be sampled.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GlyphKind {
+    /// a multi-channel distance field. sample with the median of red, green, and blue.
+    #[default]
+    Mtsdf,
+
+    /// a single-channel distance field, replicated into every channel. sample any channel directly.
+    Sdf,
+
+    /// a premultiplied color bitmap, such as an embedded bitmap or color glyph. sample directly.
+    Color,
+}
+
+impl GlyphKind {
+    /// the number of meaningful channels in each texel.
+    /// SDF texels replicate their one channel, so they still take up four bytes.
+    pub fn channels(self) -> u32 {
+        match self {
+            GlyphKind::Mtsdf | GlyphKind::Color => 4,
+            GlyphKind::Sdf => 1,
+        }
+    }
+}
+
+impl From<GlyphMode> for GlyphKind {
+    fn from(mode: GlyphMode) -> Self {
+        match mode {
+            GlyphMode::Mtsdf => GlyphKind::Mtsdf,
+            GlyphMode::Sdf => GlyphKind::Sdf,
+        }
+    }
+}
+
+/// options for turning a [GlyphShape] into a [GlyphBitmap].
+#[derive(Copy, Clone, Debug)]
+pub struct GenerateConfig {
+    /// the kind of distance field to generate.
+    pub mode: GlyphMode,
+
+    /// the fill rule used to decide which regions of the outline are inside.
+    pub fill_rule: FillRule,
+
+    /// overrides msdfgen's overlapping contour support. `None` keeps msdfgen's default.
+    /// [GlyphShape::overlap_support] takes precedence for individual glyphs.
+    pub overlap_support: Option<bool>,
+
+    /// whether msdfgen's error correction pass runs on MTSDFs.
+    /// disabling it is faster, but leaves artifacts where channels disagree near corners.
+    pub error_correction: bool,
+
+    /// the order of each texel's channels.
+    pub channel_order: ChannelOrder,
+
+    /// how distances are rounded when they are quantized.
+    pub rounding: Rounding,
+
+    /// dilates the distance field by this many texels, for faux-bold glyphs.
+    ///
+    /// this moves the edge outwards, fattening every stroke, unlike stroking which adds an outline.
+    /// large values merge adjacent strokes and fill in counters, and the dilation saturates at half the distance range.
+    pub dilation_px: f64,
+
+    /// scales stored distances about the edge before they are quantized, narrowing or widening the band they span.
+    ///
+    /// values above 1 saturate closer to the edge, which sharpens small text and reduces bleed between tiles.
+    /// values below 1 spread the field over less than the full `0.0..=1.0`, for smoother falloff in large text and effects.
+    /// either way the encoded range becomes `range / distance_scale`, so shaders must divide their
+    /// screen pixel range by this too. [crate::glyph_atlas::GlyphAtlas::range_px] already accounts for it.
+    /// must be positive and finite; see [Self::validate].
+    pub distance_scale: f32,
+}
+
+impl GenerateConfig {
+    /// returns [FontError::InvalidParameter] if [Self::distance_scale] is not positive and finite,
+    /// or if [Self::dilation_px] is not finite.
+    /// a zero scale flattens every texel onto the edge, a negative one inverts the field,
+    /// and a non-finite dilation leaves every glyph blank. atlases check this before baking.
+    pub fn validate(&self) -> FontResult<()> {
+        positive_finite("distance_scale", self.distance_scale as f64)?;
+        if !self.dilation_px.is_finite() {
+            return Err(FontError::InvalidParameter {
+                name: "dilation_px",
+                value: self.dilation_px,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// creates a bitmap filled with `background`, with this config's channel order and the kind of its mode.
+    pub(crate) fn blank_bitmap(&self, width: u32, height: u32, background: u32) -> GlyphBitmap {
+        let mut bitmap = GlyphBitmap::new_filled(width, height, background);
+        bitmap.channel_order = self.channel_order;
+        bitmap.kind = self.mode.into();
+        bitmap
+    }
+
+    /// the texel value for "fully outside" the glyph, for distance ranges of `range_px` texels.
+    ///
+    /// with the default config this is [GlyphBitmap::OUTSIDE]. a [Self::distance_scale] below 1 or a positive
+    /// [Self::dilation_px] raise it above 0, since fully outside then quantizes to more than 0.
+    /// atlases fill their backgrounds with this, so bilinear sampling at tile edges falls off towards "outside".
+    pub fn outside_texel(&self, range_px: f64) -> u32 {
+        let dilation = (self.dilation_px / range_px) as f32;
+        let outside = self.conv(0.0, dilation);
+        self.channel_order.pack([outside; 4])
+    }
+
+    /// dilates, scales and quantizes a normalized distance.
+    ///
+    /// the distance is clamped to `0.0..=1.0` first, so that everything at least half the range outside the edge
+    /// quantizes to [Self::outside_texel]. degenerate glyphs can make msdfgen emit NaNs or infinities,
+    /// which would otherwise be clamped or cast inconsistently, so every non-finite distance is treated as fully outside.
+    fn conv(&self, f: f32, dilation: f32) -> u8 {
+        let f = if f.is_finite() {
+            f.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.rounding
+            .conv(0.5 + (f + dilation - 0.5) * self.distance_scale)
+    }
+}
+
+impl Default for GenerateConfig {
+    fn default() -> Self {
+        Self {
+            mode: GlyphMode::default(),
+            fill_rule: FillRule::default(),
+            overlap_support: None,
+            error_correction: true,
+            channel_order: ChannelOrder::default(),
+            rounding: Rounding::default(),
+            dilation_px: 0.0,
+            distance_scale: 1.0,
+        }
+    }
+}
 
 pub struct GlyphShape {
     pub anchor: Vec2,
@@ -27,96 +192,829 @@ pub struct GlyphShape {
     pub width: u32,
     pub height: u32,
     pub framing: msdfgen::Framing<f64>,
+    /// overrides msdfgen's overlapping contour support for this glyph. `None` defers to [GenerateConfig::overlap_support].
+    ///
+    /// overlap support is needed for glyphs with self-intersecting or overlapping contours,
+    /// like brush-script swashes, which otherwise show artifacts where contours cross.
+    /// it makes generation noticeably slower on glyphs with many contours, so only enable it where needed.
+    pub overlap_support: Option<bool>,
+    bounds: msdfgen::Bound<f64>,
+    suspect_winding: bool,
 }
 
-impl GlyphShape {
-    pub fn new(
+/// summarizes the shape without dumping its contours.
+impl fmt::Debug for GlyphShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlyphShape")
+            .field("anchor", &self.anchor)
+            .field("px_per_em", &self.px_per_em)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("bounds", &self.bounds)
+            .field("overlap_support", &self.overlap_support)
+            .field("suspect_winding", &self.suspect_winding)
+            .finish_non_exhaustive()
+    }
+}
+
+/// a glyph's outline with its edges colored, before it is framed at a size. see [GlyphShapeBuilder::color].
+#[derive(Clone)]
+pub struct ColoredShape {
+    glyph: GlyphId,
+    units_per_em: f64,
+    shape: Shape,
+    bounds: msdfgen::Bound<f64>,
+    suspect_winding: bool,
+}
+
+impl ColoredShape {
+    /// the glyph this shape was outlined from.
+    pub fn glyph(&self) -> GlyphId {
+        self.glyph
+    }
+}
+
+/// the msdfgen strategy used to assign colors to a shape's edges.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum EdgeColoring {
+    /// the fastest strategy, which works well for most glyphs.
+    #[default]
+    Simple,
+
+    /// handles ink traps and other small corners better than [EdgeColoring::Simple].
+    InkTrap,
+
+    /// colors edges by their distance from each other, which is slower but
+    /// avoids artifacts on shapes with many corners near the angle threshold.
+    ByDistance,
+}
+
+/// how a glyph's contour winding is checked before it is colored.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Winding {
+    /// uses the outline as-is.
+    #[default]
+    Ignore,
+
+    /// checks the orientation of the outermost contour and reports it through [GlyphShape::has_suspect_winding].
+    Detect,
+
+    /// like [Winding::Detect], but also reverses every contour of a glyph with suspect winding.
+    /// this fixes glyphs that would otherwise come out inverted.
+    Fix,
+}
+
+/// configures how [GlyphShape]s are created.
+#[derive(Clone, Debug)]
+pub struct GlyphShapeBuilder {
+    pub(crate) px_per_em: f64,
+    pub(crate) range: Range<f64>,
+    angle_threshold: f64,
+    edge_coloring: EdgeColoring,
+    min_size: u32,
+    winding: Winding,
+    overlap_support: Option<bool>,
+    notdef_fallback: bool,
+    framing: Option<(msdfgen::Framing<f64>, u32, u32)>,
+}
+
+impl GlyphShapeBuilder {
+    pub const DEFAULT_ANGLE_THRESHOLD: f64 = 3.0;
+    pub const DEFAULT_MIN_SIZE: u32 = 16;
+
+    pub fn new(px_per_em: f64, range: Range<f64>) -> Self {
+        Self {
+            px_per_em,
+            range,
+            angle_threshold: Self::DEFAULT_ANGLE_THRESHOLD,
+            edge_coloring: EdgeColoring::default(),
+            min_size: Self::DEFAULT_MIN_SIZE,
+            winding: Winding::default(),
+            overlap_support: None,
+            notdef_fallback: false,
+            framing: None,
+        }
+    }
+
+    /// sets the distance range so that text drawn at `min_screen_px_per_em` or larger
+    /// has at least `screen_px_range` pixels of distance range on screen.
+    ///
+    /// MSDF shaders compute `screenPxRange = range_px * screen_px_per_em / px_per_em`, which shrinks with the
+    /// on-screen size, so this solves it for the smallest target size:
+    /// `range_px = screen_px_range * px_per_em / min_screen_px_per_em`.
+    /// a `screen_px_range` of 2 to 4 keeps edges crisp without blurring, and larger sizes only get sharper.
+    /// this assumes a [GenerateConfig::distance_scale] of 1; atlases account for other scales with
+    /// [crate::glyph_atlas::GlyphAtlasBuilder::target_screen_px_range].
+    /// returns [FontError::InvalidParameter] if any of the sizes are not positive and finite.
+    pub fn target_screen_px_range(
+        self,
+        screen_px_range: f64,
+        min_screen_px_per_em: f64,
+    ) -> FontResult<Self> {
+        self.scaled_screen_px_range(screen_px_range, min_screen_px_per_em, 1.0)
+    }
+
+    /// like [Self::target_screen_px_range], for distances that are scaled by `distance_scale` when quantized.
+    pub(crate) fn scaled_screen_px_range(
+        mut self,
+        screen_px_range: f64,
+        min_screen_px_per_em: f64,
+        distance_scale: f64,
+    ) -> FontResult<Self> {
+        let px_per_em = positive_finite("px_per_em", self.px_per_em)?;
+        let screen_px_range = positive_finite("screen_px_range", screen_px_range)?;
+        let min_screen_px_per_em = positive_finite("min_screen_px_per_em", min_screen_px_per_em)?;
+        let distance_scale = positive_finite("distance_scale", distance_scale)?;
+        self.range = Range::Px(screen_px_range * px_per_em / min_screen_px_per_em * distance_scale);
+        Ok(self)
+    }
+
+    pub fn angle_threshold(mut self, angle_threshold: f64) -> Self {
+        self.angle_threshold = angle_threshold;
+        self
+    }
+
+    /// sets the strategy used to color the shape's edges.
+    pub fn edge_coloring(mut self, edge_coloring: EdgeColoring) -> Self {
+        self.edge_coloring = edge_coloring;
+        self
+    }
+
+    /// sets the minimum width and height of a glyph's bitmap.
+    /// 0 uses each glyph's natural size: its bounds, padded by half the distance range on either side.
+    pub fn min_size(mut self, min_size: u32) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// sets whether contour winding is checked, and fixed, before coloring.
+    pub fn winding(mut self, winding: Winding) -> Self {
+        self.winding = winding;
+        self
+    }
+
+    /// sets [GlyphShape::overlap_support] on the built shapes.
+    pub fn overlap_support(mut self, overlap_support: Option<bool>) -> Self {
+        self.overlap_support = overlap_support;
+        self
+    }
+
+    /// when enabled, glyphs that are truly missing get a synthesized "tofu" box instead of an error.
+    /// a glyph is truly missing if it lies outside of the face, or if it is the .notdef glyph and has no outline.
+    /// glyphs that are merely empty, like spaces, still return a glyph shape error.
+    pub fn notdef_fallback(mut self, notdef_fallback: bool) -> Self {
+        self.notdef_fallback = notdef_fallback;
+        self
+    }
+
+    /// generates against an explicit framing and bitmap size, instead of autoframing each glyph.
+    ///
+    /// this places several glyphs in a shared coordinate frame, like the pieces of a stretchy bracket,
+    /// so that they line up when composited. [Self::min_size] is ignored.
+    ///
+    /// the framing must agree with the builder, since atlases derive quad positions and the reported distance range
+    /// from the builder's size and range. building returns [FontError::InvalidParameter] unless both scales are
+    /// `px_per_em / units_per_em`, and the framing's range (in font units) spans the builder's range in pixels.
+    pub fn framing(mut self, framing: msdfgen::Framing<f64>, width: u32, height: u32) -> Self {
+        self.framing = Some((framing, width, height));
+        self
+    }
+
+    /// returns [FontError::InvalidParameter] if the pixels per em or the range are not positive and finite.
+    pub fn build(&self, face: &Face, glyph: GlyphId) -> FontResult<GlyphShape> {
+        self.build_with_units_per_em(face.units_per_em() as f64, face, glyph)
+    }
+
+    fn build_with_units_per_em(
+        &self,
         units_per_em: f64,
-        px_per_em: f64,
-        range: Range<f64>,
-        angle_threshold: f64,
         face: &Face,
         glyph: GlyphId,
-    ) -> FontResult<Self> {
-        let mut shape = face
-            .glyph_shape(glyph)
-            .ok_or(FontError::GlyphShape(GlyphShapeError(glyph)))?;
-        shape.edge_coloring_simple(angle_threshold, 0);
-        let bounds = shape.get_bound();
+    ) -> FontResult<GlyphShape> {
+        self.validate()?;
+        let colored = self.color_with_units_per_em(units_per_em, face, glyph)?;
+        self.frame(colored)
+    }
+
+    /// outlines and colors a glyph without framing it.
+    ///
+    /// the colored shape is independent of size, so it can be framed at several sizes with [Self::build_colored]
+    /// while paying for outlining and edge coloring once. only the builder's outline and coloring options apply.
+    pub fn color(&self, face: &Face, glyph: GlyphId) -> FontResult<ColoredShape> {
+        self.color_with_units_per_em(face.units_per_em() as f64, face, glyph)
+    }
+
+    /// frames a shape from [Self::color] at this builder's size and range.
+    /// returns [FontError::InvalidParameter] if the pixels per em or the range are not positive and finite.
+    pub fn build_colored(&self, colored: &ColoredShape) -> FontResult<GlyphShape> {
+        self.validate()?;
+        self.frame(colored.clone())
+    }
+
+    fn validate(&self) -> FontResult<()> {
+        positive_finite("px_per_em", self.px_per_em)?;
+        match self.range {
+            Range::Px(value) | Range::Unit(value) => positive_finite("range", value)?,
+        };
+
+        Ok(())
+    }
+
+    fn color_with_units_per_em(
+        &self,
+        units_per_em: f64,
+        face: &Face,
+        glyph: GlyphId,
+    ) -> FontResult<ColoredShape> {
+        if !GlyphShape::UNITS_PER_EM_RANGE.contains(&units_per_em) {
+            return Err(FontError::InvalidFont("units per em out of range"));
+        }
+
+        let (mut shape, suspect_winding) = match self.outline(face, glyph) {
+            Err(FontError::GlyphShape(_))
+                if self.notdef_fallback && (glyph.0 == 0 || glyph.0 >= face.number_of_glyphs()) =>
+            {
+                let outer_clockwise = face.tables().glyf.is_some();
+                (
+                    OutlineRecorder::notdef_box(face, outer_clockwise).to_shape(),
+                    false,
+                )
+            }
+            result => result?,
+        };
+        match self.edge_coloring {
+            EdgeColoring::Simple => shape.edge_coloring_simple(self.angle_threshold, 0),
+            EdgeColoring::InkTrap => shape.edge_coloring_ink_trap(self.angle_threshold, 0),
+            EdgeColoring::ByDistance => shape.edge_coloring_by_distance(self.angle_threshold, 0),
+        }
+
+        Ok(ColoredShape {
+            glyph,
+            units_per_em,
+            bounds: shape.get_bound(),
+            shape,
+            suspect_winding,
+        })
+    }
+
+    fn frame(&self, colored: ColoredShape) -> FontResult<GlyphShape> {
+        let ColoredShape {
+            glyph,
+            units_per_em,
+            shape,
+            bounds,
+            suspect_winding,
+        } = colored;
+
+        let px_per_em = self.px_per_em;
+        let range = self.range;
         let px_per_unit = px_per_em / units_per_em;
-        let width = (bounds.width() * px_per_unit).ceil() as u32 + 8;
-        let height = (bounds.height() * px_per_unit).ceil() as u32 + 8;
-        let width = width.max(16);
-        let height = height.max(16);
-        let framing =
-            bounds
-                .autoframe(width, height, range, None)
-                .ok_or(FontError::AutoFraming {
-                    glyph,
-                    width: width as usize,
-                    height: height as usize,
-                    range,
-                })?;
+        let padding = self.padding(px_per_unit);
+        let width = (bounds.width() * px_per_unit).ceil() as u32 + padding;
+        let height = (bounds.height() * px_per_unit).ceil() as u32 + padding;
+        let width = width.max(self.min_size);
+        let height = height.max(self.min_size);
+        let (framing, width, height) = match self.framing {
+            Some(framing) => self.validate_framing(framing, px_per_unit)?,
+            None => {
+                let scale = Vector2::new(px_per_unit, px_per_unit);
+                let framing = bounds.autoframe(width, height, range, Some(scale)).ok_or(
+                    FontError::AutoFraming {
+                        glyph,
+                        width: width as usize,
+                        height: height as usize,
+                        range,
+                    },
+                )?;
+                (framing, width, height)
+            }
+        };
 
         let anchor =
             Vec2::new(framing.translate.x as f32, framing.translate.y as f32) / units_per_em as f32;
 
-        Ok(Self {
+        Ok(GlyphShape {
             anchor,
             framing,
             px_per_em,
             shape,
             width,
             height,
+            overlap_support: self.overlap_support,
+            bounds,
+            suspect_winding,
         })
     }
 
+    /// the texels added to each dimension of a glyph's bounds, so that the distance range fits around it.
+    /// the range is split between both sides. glyphs are framed at exactly `px_per_unit` and centered,
+    /// so rounding the bounds up and [Self::min_size] only add margin, and never rescale the glyph.
+    fn padding(&self, px_per_unit: f64) -> u32 {
+        self.range_px(px_per_unit).ceil() as u32
+    }
+
+    fn range_px(&self, px_per_unit: f64) -> f64 {
+        match self.range {
+            Range::Px(px) => px,
+            Range::Unit(units) => units * px_per_unit,
+        }
+    }
+
+    fn validate_framing(
+        &self,
+        (framing, width, height): (msdfgen::Framing<f64>, u32, u32),
+        px_per_unit: f64,
+    ) -> FontResult<(msdfgen::Framing<f64>, u32, u32)> {
+        positive_finite("width", width as f64)?;
+        positive_finite("height", height as f64)?;
+        positive_finite("framing range", framing.range)?;
+        positive_finite("framing x scale", framing.scale.x)?;
+        positive_finite("framing y scale", framing.scale.y)?;
+        if !framing.translate.x.is_finite() {
+            return Err(FontError::InvalidParameter {
+                name: "framing x translation",
+                value: framing.translate.x,
+            });
+        }
+        if !framing.translate.y.is_finite() {
+            return Err(FontError::InvalidParameter {
+                name: "framing y translation",
+                value: framing.translate.y,
+            });
+        }
+
+        let matches = |value: f64, expected: f64| (value - expected).abs() <= expected * 1e-9;
+        if !matches(framing.scale.x, px_per_unit) {
+            return Err(FontError::InvalidParameter {
+                name: "framing x scale",
+                value: framing.scale.x,
+            });
+        }
+        if !matches(framing.scale.y, px_per_unit) {
+            return Err(FontError::InvalidParameter {
+                name: "framing y scale",
+                value: framing.scale.y,
+            });
+        }
+
+        let range_px = self.range_px(px_per_unit);
+        if !matches(framing.range * framing.scale.x, range_px) {
+            return Err(FontError::InvalidParameter {
+                name: "framing range",
+                value: framing.range,
+            });
+        }
+
+        Ok((framing, width, height))
+    }
+
+    fn outline(&self, face: &Face, glyph: GlyphId) -> FontResult<(Shape, bool)> {
+        let missing = || FontError::GlyphShape(GlyphShapeError(glyph));
+        if self.winding == Winding::Ignore {
+            return Ok((face.glyph_shape(glyph).ok_or_else(missing)?, false));
+        }
+
+        let mut recorder = OutlineRecorder::default();
+        face.outline_glyph(glyph, &mut recorder)
+            .ok_or_else(missing)?;
+
+        let outer_clockwise = face.tables().glyf.is_some();
+        let suspect_winding = recorder.has_suspect_winding(outer_clockwise);
+        if suspect_winding && self.winding == Winding::Fix {
+            recorder.reverse();
+            return Ok((recorder.to_shape(), true));
+        }
+
+        let shape = face.glyph_shape(glyph).ok_or_else(missing)?;
+        Ok((shape, suspect_winding))
+    }
+}
+
+fn positive_finite(name: &'static str, value: f64) -> FontResult<f64> {
+    if value.is_finite() && value > 0.0 {
+        Ok(value)
+    } else {
+        Err(FontError::InvalidParameter { name, value })
+    }
+}
+
+impl GlyphShape {
+    /// the range of units per em allowed by the OpenType specification.
+    pub const UNITS_PER_EM_RANGE: std::ops::RangeInclusive<f64> = 16.0..=16384.0;
+
+    pub fn new(
+        units_per_em: f64,
+        px_per_em: f64,
+        range: Range<f64>,
+        angle_threshold: f64,
+        face: &Face,
+        glyph: GlyphId,
+    ) -> FontResult<Self> {
+        GlyphShapeBuilder::new(px_per_em, range)
+            .angle_threshold(angle_threshold)
+            .build_with_units_per_em(units_per_em, face, glyph)
+    }
+
+    pub fn builder(px_per_em: f64, range: Range<f64>) -> GlyphShapeBuilder {
+        GlyphShapeBuilder::new(px_per_em, range)
+    }
+
+    /// creates a glyph shape for a size in points on a display with the given DPI.
+    /// a point is 1/72 of an inch, so this uses `px_per_em = point_size * dpi / 72`.
+    pub fn for_point_size(
+        face: &Face,
+        point_size: f64,
+        dpi: f64,
+        range: Range<f64>,
+        glyph: GlyphId,
+    ) -> FontResult<Self> {
+        GlyphShapeBuilder::new(point_size * dpi / 72.0, range).build(face, glyph)
+    }
+
+    /// the glyph's untransformed bounding box in font units.
+    pub fn bounds(&self) -> msdfgen::Bound<f64> {
+        self.bounds
+    }
+
+    /// returns true if the glyph's outer contour is wound against the font's convention.
+    /// this is only checked when the shape was built with [Winding::Detect] or [Winding::Fix].
+    pub fn has_suspect_winding(&self) -> bool {
+        self.suspect_winding
+    }
+
+    /// the width of the distance range in texels, as framed for this glyph.
+    /// shaders should use this (scaled to screen pixels) for their antialiasing width,
+    /// divided by [GenerateConfig::distance_scale] if the bitmap was generated with one other than 1.
+    pub fn effective_range_px(&self) -> f64 {
+        self.framing.range * self.framing.scale.x
+    }
+
     pub fn generate(&self) -> GlyphBitmap {
-        let config: MsdfGeneratorConfig = MsdfGeneratorConfig::default();
-        let width = self.width;
-        let height = self.height;
+        self.generate_with(&GenerateConfig::default())
+    }
+
+    /// generates a bitmap as described by `config`.
+    pub fn generate_with(&self, config: &GenerateConfig) -> GlyphBitmap {
+        match config.mode {
+            GlyphMode::Mtsdf => {
+                let mut bitmap = Bitmap::<Rgba<f32>>::new(self.width, self.height);
+                self.render(&mut bitmap, config);
+                self.quantize(&bitmap, config)
+            }
+            GlyphMode::Sdf => self.render_sdf(config),
+        }
+    }
+
+    /// like [Self::generate_with], but also counts the texels that msdfgen's error correction changed.
+    ///
+    /// a nonzero count points at a shape worth tuning the angle threshold or edge coloring for.
+    /// the count is always 0 for SDFs, and when [GenerateConfig::error_correction] is disabled.
+    /// this keeps a copy of the uncorrected field to diff against, so [Self::generate_with] pays nothing for it.
+    pub fn generate_counting_corrections(&self, config: &GenerateConfig) -> (GlyphBitmap, usize) {
+        if config.mode != GlyphMode::Mtsdf || !config.error_correction {
+            return (self.generate_with(config), 0);
+        }
+
+        let uncorrected = GenerateConfig {
+            error_correction: false,
+            ..*config
+        };
+
+        let mut bitmap = Bitmap::<Rgba<f32>>::new(self.width, self.height);
+        self.render(&mut bitmap, &uncorrected);
+        let texel = |p: &Rgba<f32>| [p.r, p.g, p.b, p.a];
+        let before: Vec<_> = bitmap.pixels().iter().map(texel).collect();
+
+        let msdf_config = self.msdf_config(config);
+        self.shape
+            .correct_msdf_error(&mut bitmap, &self.framing, msdf_config);
+
+        let changed = bitmap
+            .pixels()
+            .iter()
+            .zip(before)
+            .filter(|(after, before)| texel(after) != *before)
+            .count();
+
+        (self.quantize(&bitmap, config), changed)
+    }
+
+    /// generates the MTSDF without quantizing it, for inspecting msdfgen's exact output.
+    pub fn generate_raw(&self) -> Bitmap<Rgba<f32>> {
+        let mut bitmap = Bitmap::<Rgba<f32>>::new(self.width, self.height);
+        self.render(&mut bitmap, &GenerateConfig::default());
+        bitmap
+    }
+
+    /// like [Self::generate_with], but renders into `scratch` instead of allocating a new float bitmap.
+    /// scratch bitmaps only hold MTSDFs, so other modes allocate as usual.
+    pub fn generate_with_scratch(
+        &self,
+        scratch: &mut ScratchBitmap,
+        config: &GenerateConfig,
+    ) -> GlyphBitmap {
+        if config.mode != GlyphMode::Mtsdf {
+            return self.generate_with(config);
+        }
+
+        let bitmap = scratch.get(self.width, self.height);
+        self.render(bitmap, config);
+        self.quantize(bitmap, config)
+    }
+
+    /// like [Self::generate_with], but renders into a scratch bitmap owned by the current thread.
+    /// this is meant to be called from inside a rayon `par_iter` or any other worker thread.
+    /// see [ScratchBitmap] for when the scratch bitmap is actually reused.
+    pub fn generate_pooled(&self, config: &GenerateConfig) -> GlyphBitmap {
+        thread_local! {
+            static SCRATCH: std::cell::RefCell<ScratchBitmap> = Default::default();
+        }
+
+        SCRATCH.with(|scratch| self.generate_with_scratch(&mut scratch.borrow_mut(), config))
+    }
+
+    /// converts a dilation in texels into normalized distance units.
+    fn dilation(&self, px: f64) -> f32 {
+        (px / self.effective_range_px()) as f32
+    }
+
+    fn msdf_config(&self, config: &GenerateConfig) -> MsdfGeneratorConfig {
+        let mut msdf_config: MsdfGeneratorConfig = MsdfGeneratorConfig::default();
+        if let Some(overlap_support) = self.overlap_support.or(config.overlap_support) {
+            msdf_config.set_overlap_support(overlap_support);
+        }
+
+        msdf_config
+    }
+
+    fn render(&self, bitmap: &mut Bitmap<Rgba<f32>>, config: &GenerateConfig) {
+        let msdf_config = self.msdf_config(config);
         let framing = &self.framing;
         let shape = &self.shape;
-        let mut bitmap = Bitmap::<Rgba<f32>>::new(width, height);
-        shape.generate_mtsdf(&mut bitmap, framing, config);
-        shape.correct_sign(&mut bitmap, framing, FillRule::default());
-        shape.correct_msdf_error(&mut bitmap, framing, config);
+        shape.generate_mtsdf(bitmap, framing, msdf_config);
+        shape.correct_sign(bitmap, framing, config.fill_rule);
+
+        if config.error_correction {
+            shape.correct_msdf_error(bitmap, framing, msdf_config);
+        }
+    }
 
+    fn quantize(&self, bitmap: &Bitmap<Rgba<f32>>, config: &GenerateConfig) -> GlyphBitmap {
+        let dilation = self.dilation(config.dilation_px);
+        let conv = |f| config.conv(f, dilation);
+        let channel_order = config.channel_order;
         let data = bitmap
             .pixels()
             .iter()
-            .map(|p| {
-                fn conv(f: f32) -> u32 {
-                    (f * 256.0).round() as u8 as _
-                }
+            .map(|p| channel_order.pack([conv(p.r), conv(p.g), conv(p.b), conv(p.a)]))
+            .collect();
 
-                (conv(p.r) << 24) | (conv(p.g) << 16) | (conv(p.b) << 8) | conv(p.a)
-            })
+        GlyphBitmap {
+            data,
+            width: self.width,
+            height: self.height,
+            channel_order,
+            kind: GlyphKind::Mtsdf,
+            stride: self.width,
+        }
+    }
+
+    fn render_sdf(&self, config: &GenerateConfig) -> GlyphBitmap {
+        let mut sdf_config = GeneratorConfig::default();
+        if let Some(overlap_support) = self.overlap_support.or(config.overlap_support) {
+            sdf_config.set_overlap_support(overlap_support);
+        }
+
+        let framing = &self.framing;
+        let shape = &self.shape;
+        let mut bitmap = Bitmap::<Gray<f32>>::new(self.width, self.height);
+        shape.generate_sdf(&mut bitmap, framing, sdf_config);
+        shape.correct_sign(&mut bitmap, framing, config.fill_rule);
+
+        let dilation = self.dilation(config.dilation_px);
+        let data = bitmap
+            .pixels()
+            .iter()
+            .map(|p| u32::from_ne_bytes([config.conv(p.v, dilation); 4]))
             .collect();
 
         GlyphBitmap {
             data,
-            width,
-            height,
+            width: self.width,
+            height: self.height,
+            channel_order: config.channel_order,
+            kind: GlyphKind::Sdf,
+            stride: self.width,
+        }
+    }
+}
+
+/// a float bitmap that is reused across glyph generations.
+///
+/// msdfgen's [Bitmap] has fixed dimensions and renders and error-corrects every one of its texels,
+/// so it can't render a glyph into part of a larger buffer. a scratch bitmap is therefore only reused
+/// while consecutive glyphs have exactly the same size, and is reallocated whenever the size changes.
+/// glyph sizes follow their bounds, so that mostly happens for glyphs clamped up to [GlyphShapeBuilder::min_size]
+/// and for runs of repeated or uniform glyphs, like digits in a monospace face or icons.
+#[derive(Default)]
+pub struct ScratchBitmap {
+    bitmap: Option<Bitmap<Rgba<f32>>>,
+}
+
+impl ScratchBitmap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn get(&mut self, width: u32, height: u32) -> &mut Bitmap<Rgba<f32>> {
+        let reusable = self
+            .bitmap
+            .as_ref()
+            .map(|bitmap| bitmap.width() == width && bitmap.height() == height)
+            .unwrap_or(false);
+
+        if !reusable {
+            self.bitmap = Some(Bitmap::new(width, height));
+        }
+
+        self.bitmap.as_mut().unwrap()
+    }
+}
+
+/// how normalized distances are rounded when they are quantized into bytes.
+/// distances are scaled by 256 before rounding, then saturated to `0..=255`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Rounding {
+    /// rounds to the nearest byte.
+    #[default]
+    Round,
+
+    /// truncates, matching tools that cast floats to integers directly.
+    Floor,
+
+    /// rounds up.
+    Ceil,
+}
+
+impl Rounding {
+    /// quantizes a normalized distance into a byte.
+    fn conv(self, f: f32) -> u8 {
+        let f = f * 256.0;
+        let f = match self {
+            Rounding::Round => f.round(),
+            Rounding::Floor => f.floor(),
+            Rounding::Ceil => f.ceil(),
+        };
+
+        f as u8
+    }
+}
+
+/// the order of a texel's channels in memory, as seen through [GlyphBitmap::data_bytes].
+/// pick the order that matches the texture format being uploaded to.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ChannelOrder {
+    #[default]
+    Rgba,
+    Bgra,
+    Argb,
+}
+
+impl ChannelOrder {
+    /// packs red, green, blue, and alpha channels into a texel.
+    pub fn pack(self, [r, g, b, a]: [u8; 4]) -> u32 {
+        let bytes = match self {
+            ChannelOrder::Rgba => [r, g, b, a],
+            ChannelOrder::Bgra => [b, g, r, a],
+            ChannelOrder::Argb => [a, r, g, b],
+        };
+
+        u32::from_ne_bytes(bytes)
+    }
+
+    /// unpacks a texel into red, green, blue, and alpha channels.
+    pub fn unpack(self, texel: u32) -> [u8; 4] {
+        let bytes = texel.to_ne_bytes();
+        match self {
+            ChannelOrder::Rgba => bytes,
+            ChannelOrder::Bgra => [bytes[2], bytes[1], bytes[0], bytes[3]],
+            ChannelOrder::Argb => [bytes[1], bytes[2], bytes[3], bytes[0]],
         }
     }
 }
 
+/// bitmaps compare and hash by their dimensions, channel layout, kind, and texels,
+/// so they can be used as keys for deduplicating identical tiles.
+#[derive(PartialEq, Eq, Hash)]
 pub struct GlyphBitmap {
+    /// the texels, row by row. rows are [Self::row_stride] bytes apart, which may include padding after each row.
     pub data: Vec<u32>,
     pub width: u32,
     pub height: u32,
+    pub channel_order: ChannelOrder,
+    pub kind: GlyphKind,
+    stride: u32,
+}
+
+/// summarizes the bitmap without dumping its texels.
+impl fmt::Debug for GlyphBitmap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlyphBitmap")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("channel_order", &self.channel_order)
+            .field("kind", &self.kind)
+            .field("stride", &self.stride)
+            .finish_non_exhaustive()
+    }
 }
 
 impl GlyphBitmap {
+    /// the texel value for "fully outside" the glyph, with the default [GenerateConfig].
+    ///
+    /// both MTSDF and SDF channels store `0.5 + distance / range`, clamped to `0.0..=1.0`, where distance is positive inside.
+    /// anything at least half the range outside the edge quantizes to 0 in every channel.
+    /// background texels should hold this value so bilinear sampling at tile edges falls off towards "outside".
+    /// configs that scale or dilate distances move it; see [GenerateConfig::outside_texel].
+    pub const OUTSIDE: u32 = 0;
+
     pub fn new(width: u32, height: u32) -> Self {
+        Self::new_filled(width, height, Self::OUTSIDE)
+    }
+
+    /// creates a bitmap with every texel set to `value`.
+    /// the bitmap's kind defaults to [GlyphKind::Mtsdf]; set [Self::kind] for other contents.
+    /// panics if the number of texels overflows `usize`.
+    pub fn new_filled(width: u32, height: u32, value: u32) -> Self {
+        Self::new_strided(width, height, width, value)
+    }
+
+    /// creates a bitmap whose rows each start on a multiple of `alignment` bytes, filled with [Self::OUTSIDE].
+    ///
+    /// this lets the bitmap be uploaded directly to backends with row alignment requirements,
+    /// such as wgpu's 256-byte `COPY_BYTES_PER_ROW_ALIGNMENT`. the padding after each row also holds [Self::OUTSIDE].
+    /// [crate::glyph_atlas::GlyphAtlas::generate_full_into] bakes a whole atlas straight into such a bitmap.
+    /// panics if `alignment` is not a nonzero multiple of 4, or if the number of texels overflows `usize`.
+    pub fn with_row_alignment(width: u32, height: u32, alignment: u32) -> Self {
+        assert!(
+            alignment > 0 && alignment.is_multiple_of(4),
+            "row alignment {} is not a nonzero multiple of 4",
+            alignment
+        );
+
+        let alignment = alignment / 4;
+        let stride = width
+            .checked_next_multiple_of(alignment)
+            .unwrap_or_else(|| panic!("{}x{} bitmap is too large", width, height));
+        Self::new_strided(width, height, stride, Self::OUTSIDE)
+    }
+
+    fn new_strided(width: u32, height: u32, stride: u32, value: u32) -> Self {
+        let len = Self::texel_count(stride, height)
+            .unwrap_or_else(|| panic!("{}x{} bitmap is too large", width, height));
+
         Self {
-            data: vec![0; (width * height) as usize],
+            data: vec![value; len],
             width,
             height,
+            channel_order: ChannelOrder::default(),
+            kind: GlyphKind::default(),
+            stride,
         }
     }
 
+    /// the number of texels in a bitmap, or `None` if its bytes could not be addressed.
+    fn texel_count(stride: u32, height: u32) -> Option<usize> {
+        let len = (stride as usize).checked_mul(height as usize)?;
+        let bytes = len.checked_mul(4)?;
+        (bytes <= isize::MAX as usize).then_some(len)
+    }
+
+    /// the distance in bytes between the starts of consecutive rows in [Self::data_bytes].
+    /// this is `width * 4` unless the bitmap was made with [Self::with_row_alignment].
+    pub fn row_stride(&self) -> usize {
+        self.stride as usize * 4
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        y as usize * self.stride as usize + x as usize
+    }
+
+    /// iterates over the texels of each row, without padding.
+    fn texel_rows(&self) -> impl Iterator<Item = &[u32]> {
+        let width = self.width as usize;
+        self.data
+            .chunks(self.stride.max(1) as usize)
+            .map(move |row| &row[..width])
+    }
+
+    /// sets every texel to `value`.
+    pub fn fill(&mut self, value: u32) {
+        self.data.fill(value);
+    }
+
+    /// the texels as bytes, with each texel's channels laid out in [Self::channel_order].
+    /// rows are [Self::row_stride] bytes apart.
     pub fn data_bytes(&self) -> &[u8] {
         unsafe {
             let ptr = self.data.as_ptr();
@@ -126,19 +1024,612 @@ impl GlyphBitmap {
         }
     }
 
+    /// copies the texels into bytes that are always red, green, blue, then alpha, whatever the [Self::channel_order].
+    /// unlike [Self::data_bytes], the layout does not depend on the platform, which suits FFI and serialization.
+    /// rows are always tightly packed, without any padding from [Self::with_row_alignment].
+    pub fn to_rgba_bytes(&self) -> Vec<u8> {
+        self.texel_rows()
+            .flatten()
+            .flat_map(|texel| self.channel_order.unpack(*texel))
+            .collect()
+    }
+
+    /// borrows a rectangle of the bitmap without copying it, or `None` if the rectangle is out of bounds.
+    pub fn view(&self, x: u32, y: u32, width: u32, height: u32) -> Option<GlyphBitmapView<'_>> {
+        if x.checked_add(width)? > self.width || y.checked_add(height)? > self.height {
+            return None;
+        }
+
+        Some(GlyphBitmapView {
+            bitmap: self,
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    /// returns true if every texel's alpha channel is below `threshold`.
+    /// for MTSDF bitmaps the alpha channel holds the true distance, so this means no texel reaches the glyph's ink.
+    pub fn is_blank(&self, threshold: u8) -> bool {
+        self.texel_rows()
+            .flatten()
+            .all(|texel| self.channel_order.unpack(*texel)[3] < threshold)
+    }
+
+    /// the fraction of texels inside the glyph, from 0 to 1.
+    ///
+    /// MTSDF texels are inside when the median of their color channels is above 0.5, SDF texels when their
+    /// distance is above 0.5, and color texels when their alpha is above half. an empty bitmap has no coverage.
+    /// this is a continuous counterpart to [Self::is_blank], for heuristics like packing sparse glyphs tighter.
+    pub fn coverage(&self) -> f32 {
+        if self.width == 0 || self.height == 0 {
+            return 0.0;
+        }
+
+        let inside = self
+            .texel_rows()
+            .flatten()
+            .filter(|texel| {
+                let [r, g, b, a] = self.channel_order.unpack(**texel);
+                let value = match self.kind {
+                    GlyphKind::Mtsdf => r.max(g).min(r.min(g).max(b)),
+                    GlyphKind::Sdf => r,
+                    GlyphKind::Color => a,
+                };
+                value > 128
+            })
+            .count();
+
+        inside as f32 / (self.width as f32 * self.height as f32)
+    }
+
+    /// shifts every distance in the bitmap by `bias`, in units of the distance range.
+    ///
+    /// positive values move the edge outwards, which thickens strokes. this is useful for "stem darkening"
+    /// thin fonts at small UI sizes, trading geometric accuracy for perceived legibility.
+    /// to move the edge by `px` texels, pass `px / range_px` (see [GlyphShape::effective_range_px]).
+    /// values saturate at fully inside and fully outside.
+    /// [GlyphKind::Color] bitmaps hold no distances, so they are left unchanged, as is any row padding.
+    pub fn bias_distance(&mut self, bias: f32) {
+        if self.kind == GlyphKind::Color {
+            return;
+        }
+
+        let bias = (bias * 256.0).round() as i32;
+        let width = self.width as usize;
+        for row in self.data.chunks_mut(self.stride.max(1) as usize) {
+            for texel in row[..width].iter_mut() {
+                let channels = texel
+                    .to_ne_bytes()
+                    .map(|channel| (channel as i32 + bias).clamp(0, 255) as u8);
+                *texel = u32::from_ne_bytes(channels);
+            }
+        }
+    }
+
+    /// bilinearly samples the bitmap at normalized coordinates, the same way a GPU would.
+    ///
+    /// `u` and `v` are in the same space as [crate::glyph_atlas::GlyphVertex::tex_coords], so `v = 0` is the first row.
+    /// coordinates outside the bitmap are clamped to its edges.
+    /// MTSDF bitmaps return the median of the filtered color channels, SDF bitmaps their distance,
+    /// and color bitmaps their alpha. for distance fields, values above 0.5 are inside the glyph.
+    pub fn sample_distance(&self, u: f32, v: f32) -> f32 {
+        if self.width == 0 || self.height == 0 {
+            return 0.0;
+        }
+
+        let max_x = (self.width - 1) as f32;
+        let max_y = (self.height - 1) as f32;
+        let x = (u * self.width as f32 - 0.5).clamp(0.0, max_x);
+        let y = (v * self.height as f32 - 0.5).clamp(0.0, max_y);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as u32, y0 as u32);
+        let (x1, y1) = ((x0 + 1).min(self.width - 1), (y0 + 1).min(self.height - 1));
+
+        let texel = |x: u32, y: u32| {
+            let texel = self.data[self.index(x, y)];
+            self.channel_order.unpack(texel).map(|c| c as f32 / 256.0)
+        };
+
+        let lerp =
+            |a: [f32; 4], b: [f32; 4], t: f32| [0, 1, 2, 3].map(|i| a[i] + (b[i] - a[i]) * t);
+
+        let bottom = lerp(texel(x0, y0), texel(x1, y0), fx);
+        let top = lerp(texel(x0, y1), texel(x1, y1), fx);
+        let [r, g, b, a] = lerp(bottom, top, fy);
+
+        match self.kind {
+            GlyphKind::Mtsdf => r.max(g).min(r.min(g).max(b)),
+            GlyphKind::Sdf => r,
+            GlyphKind::Color => a,
+        }
+    }
+
+    /// copies one channel of this bitmap into a new single-channel [GlyphKind::Sdf] bitmap.
+    ///
+    /// `channel` indexes red, green, blue, and alpha, regardless of [Self::channel_order].
+    /// extracting the alpha channel of an MTSDF gives the true-distance SDF, so one bake can feed both atlases.
+    /// the channel is replicated like any SDF; to upload it as a one-byte format, take every fourth byte of [Self::data_bytes].
+    /// panics if `channel` is not below 4.
+    pub fn extract_channel(&self, channel: usize) -> GlyphBitmap {
+        assert!(channel < 4, "channel {} out of range", channel);
+        let data = self
+            .data
+            .iter()
+            .map(|texel| u32::from_ne_bytes([self.channel_order.unpack(*texel)[channel]; 4]))
+            .collect();
+
+        GlyphBitmap {
+            data,
+            width: self.width,
+            height: self.height,
+            channel_order: self.channel_order,
+            kind: GlyphKind::Sdf,
+            stride: self.stride,
+        }
+    }
+
+    /// the number of meaningful channels in each texel. see [GlyphKind::channels].
+    pub fn channels(&self) -> u32 {
+        self.kind.channels()
+    }
+
+    /// copies this bitmap into `dst` with its first texel at `(x, y)`.
+    ///
+    /// panics if this bitmap does not fit, or if the bitmaps have different channel counts.
+    /// see [Self::try_copy_to] for details.
     pub fn copy_to(&self, dst: &mut GlyphBitmap, x: u32, y: u32) {
-        if self.width + x > dst.width || self.height + y > dst.height {
-            panic!("copy_to out-of-bounds");
-        }
-
-        let mut src_cursor = 0;
-        let mut dst_cursor = (y * dst.width + x) as usize;
-        for _ in 0..self.height {
-            let src_range = src_cursor..(src_cursor + self.width as usize);
-            let dst_range = dst_cursor..(dst_cursor + self.width as usize);
-            dst.data[dst_range].copy_from_slice(&self.data[src_range]);
-            src_cursor += self.width as usize;
-            dst_cursor += dst.width as usize;
+        if let Err(err) = self.try_copy_to(dst, x, y) {
+            panic!("copy_to failed: {}", err);
+        }
+    }
+
+    /// copies this bitmap into `dst` with its first texel at `(x, y)`.
+    ///
+    /// returns [FontError::OutOfBounds] if this bitmap does not fit, and [FontError::ChannelMismatch]
+    /// if the bitmaps have different channel counts, such as an SDF copied into an MTSDF atlas.
+    /// bitmaps with the same channel count but different [Self::channel_order]s are converted to `dst`'s order.
+    pub fn try_copy_to(&self, dst: &mut GlyphBitmap, x: u32, y: u32) -> FontResult<()> {
+        let fits_x = x
+            .checked_add(self.width)
+            .is_some_and(|right| right <= dst.width);
+        let fits_y = y
+            .checked_add(self.height)
+            .is_some_and(|top| top <= dst.height);
+        if !fits_x || !fits_y {
+            return Err(FontError::OutOfBounds {
+                x,
+                y,
+                width: self.width,
+                height: self.height,
+            });
+        }
+
+        if self.channels() != dst.channels() {
+            return Err(FontError::ChannelMismatch {
+                src: self.channels(),
+                dst: dst.channels(),
+            });
+        }
+
+        self.copy_rect(dst, (0, 0), (x, y), (self.width, self.height));
+
+        Ok(())
+    }
+
+    /// like [Self::copy_to], but only copies the part of this bitmap that overlaps `dst`.
+    ///
+    /// `(x, y)` may be negative or past the edges of `dst`, which clips this bitmap's left or bottom edges
+    /// and its right or top edges respectively. a bitmap entirely outside of `dst` copies nothing.
+    /// panics if the bitmaps have different channel counts.
+    pub fn copy_to_clipped(&self, dst: &mut GlyphBitmap, x: i32, y: i32) {
+        if self.channels() != dst.channels() {
+            panic!(
+                "copy_to_clipped failed: {}",
+                FontError::ChannelMismatch {
+                    src: self.channels(),
+                    dst: dst.channels(),
+                }
+            );
+        }
+
+        let (x, y) = (x as i64, y as i64);
+        let left = x.max(0);
+        let bottom = y.max(0);
+        let right = (x + self.width as i64).min(dst.width as i64);
+        let top = (y + self.height as i64).min(dst.height as i64);
+        if left >= right || bottom >= top {
+            return;
+        }
+
+        let src = ((left - x) as u32, (bottom - y) as u32);
+        let size = ((right - left) as u32, (top - bottom) as u32);
+        self.copy_rect(dst, src, (left as u32, bottom as u32), size);
+    }
+
+    /// copies a rectangle that is known to be in bounds of both bitmaps, converting channel orders if needed.
+    fn copy_rect(
+        &self,
+        dst: &mut GlyphBitmap,
+        (src_x, src_y): (u32, u32),
+        (dst_x, dst_y): (u32, u32),
+        (width, height): (u32, u32),
+    ) {
+        let width = width as usize;
+        let mut src_cursor = self.index(src_x, src_y);
+        let mut dst_cursor = dst.index(dst_x, dst_y);
+        for _ in 0..height {
+            let src_range = src_cursor..(src_cursor + width);
+            let dst_range = dst_cursor..(dst_cursor + width);
+            if self.channel_order == dst.channel_order {
+                dst.data[dst_range].copy_from_slice(&self.data[src_range]);
+            } else {
+                let src = &self.data[src_range];
+                for (dst_texel, src_texel) in dst.data[dst_range].iter_mut().zip(src) {
+                    let channels = self.channel_order.unpack(*src_texel);
+                    *dst_texel = dst.channel_order.pack(channels);
+                }
+            }
+
+            src_cursor += self.stride as usize;
+            dst_cursor += dst.stride as usize;
+        }
+    }
+}
+
+/// a borrowed rectangle within a [GlyphBitmap], such as a dirty region of an atlas.
+///
+/// a view's rows are not contiguous in memory, so instead of one byte slice it yields a slice per row.
+#[derive(Copy, Clone)]
+pub struct GlyphBitmapView<'a> {
+    bitmap: &'a GlyphBitmap,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl<'a> GlyphBitmapView<'a> {
+    /// the bitmap this view borrows from.
+    pub fn bitmap(&self) -> &'a GlyphBitmap {
+        self.bitmap
+    }
+
+    /// the distance in bytes between the starts of consecutive rows in the underlying bitmap.
+    pub fn stride_bytes(&self) -> usize {
+        self.bitmap.row_stride()
+    }
+
+    /// iterates over the texels of each row of the view.
+    pub fn row_texels(&self) -> impl Iterator<Item = &'a [u32]> + 'a {
+        let bitmap = self.bitmap;
+        let (x, width) = (self.x as usize, self.width as usize);
+        (self.y..self.y + self.height).map(move |row| {
+            let start = bitmap.index(0, row) + x;
+            &bitmap.data[start..start + width]
+        })
+    }
+
+    /// iterates over the bytes of each row of the view, laid out like [GlyphBitmap::data_bytes].
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> + 'a {
+        let bytes = self.bitmap.data_bytes();
+        let stride = self.stride_bytes();
+        let (x, width) = (self.x as usize * 4, self.width as usize * 4);
+        (self.y..self.y + self.height).map(move |row| {
+            let start = row as usize * stride + x;
+            &bytes[start..start + width]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONONOKI: &[u8] = include_bytes!("../examples/mononoki-Regular.ttf");
+
+    fn mononoki() -> Face<'static> {
+        Face::parse(MONONOKI, 0).unwrap()
+    }
+
+    fn glyph(face: &Face, c: char) -> GlyphId {
+        face.glyph_index(c).unwrap()
+    }
+
+    #[test]
+    fn units_per_em_out_of_range() {
+        let face = mononoki();
+        let a = glyph(&face, 'A');
+        for units_per_em in [0.0, 15.0, 16385.0, f64::NAN] {
+            let result = GlyphShape::new(units_per_em, 32.0, Range::Px(4.0), 3.0, &face, a);
+            assert!(
+                matches!(result, Err(FontError::InvalidFont(_))),
+                "units per em {} was accepted",
+                units_per_em
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_size_and_range() {
+        let face = mononoki();
+        let a = glyph(&face, 'A');
+        let cases = [
+            (0.0, Range::Px(4.0), "px_per_em"),
+            (-32.0, Range::Px(4.0), "px_per_em"),
+            (32.0, Range::Px(f64::NAN), "range"),
+        ];
+
+        for (px_per_em, range, expected) in cases {
+            let result = GlyphShape::builder(px_per_em, range).build(&face, a);
+            assert!(
+                matches!(result, Err(FontError::InvalidParameter { name, .. }) if name == expected),
+                "{} {:?} was not rejected as an invalid {}",
+                px_per_em,
+                range,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn non_finite_distances_quantize_outside() {
+        let face = mononoki();
+        let shape = GlyphShape::builder(32.0, Range::Px(4.0))
+            .build(&face, glyph(&face, 'A'))
+            .unwrap();
+
+        let mut bitmap = Bitmap::<Rgba<f32>>::new(shape.width, shape.height);
+        let values = [f32::NAN, f32::INFINITY, f32::NEG_INFINITY];
+        for (pixel, value) in bitmap.pixels_mut().iter_mut().zip(values.iter().cycle()) {
+            pixel.r = *value;
+            pixel.g = *value;
+            pixel.b = *value;
+            pixel.a = *value;
+        }
+
+        let quantized = shape.quantize(&bitmap, &GenerateConfig::default());
+        assert!(quantized
+            .data
+            .iter()
+            .all(|texel| *texel == GlyphBitmap::OUTSIDE));
+
+        let config = GenerateConfig {
+            distance_scale: 0.5,
+            ..Default::default()
+        };
+        let outside = config.outside_texel(shape.effective_range_px());
+        assert_eq!(outside, u32::from_ne_bytes([64; 4]));
+        let quantized = shape.quantize(&bitmap, &config);
+        assert!(quantized.data.iter().all(|texel| *texel == outside));
+    }
+
+    #[test]
+    fn copy_clipped_on_the_left() {
+        let src = GlyphBitmap::new_filled(4, 2, 0xffffffff);
+        let mut dst = GlyphBitmap::new(4, 4);
+        src.copy_to_clipped(&mut dst, -2, 1);
+
+        for y in 0..4 {
+            for x in 0..4 {
+                let expected = if x < 2 && (1..3).contains(&y) {
+                    0xffffffff
+                } else {
+                    GlyphBitmap::OUTSIDE
+                };
+                assert_eq!(dst.data[dst.index(x, y)], expected, "texel ({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn copy_clipped_entirely_outside() {
+        let src = GlyphBitmap::new_filled(4, 4, 0xffffffff);
+        let mut dst = GlyphBitmap::new(4, 4);
+        for (x, y) in [(-4, 0), (4, 0), (0, -4), (0, 4), (-10, 10)] {
+            src.copy_to_clipped(&mut dst, x, y);
+        }
+
+        assert_eq!(dst, GlyphBitmap::new(4, 4));
+    }
+
+    #[test]
+    fn copy_sdf_into_mtsdf() {
+        let mut src = GlyphBitmap::new_filled(2, 2, 0xffffffff);
+        src.kind = GlyphKind::Sdf;
+        let mut dst = GlyphBitmap::new(4, 4);
+
+        let result = src.try_copy_to(&mut dst, 0, 0);
+        assert!(matches!(
+            result,
+            Err(FontError::ChannelMismatch { src: 1, dst: 4 })
+        ));
+        assert_eq!(dst, GlyphBitmap::new(4, 4));
+    }
+
+    #[test]
+    fn texel_count_past_u32() {
+        if usize::BITS == 64 {
+            let count = GlyphBitmap::texel_count(46341, 46341).map(|count| count as u64);
+            assert_eq!(count, Some(46341 * 46341));
+        }
+
+        assert_eq!(GlyphBitmap::texel_count(u32::MAX, 0), Some(0));
+        assert_eq!(GlyphBitmap::texel_count(u32::MAX, u32::MAX), None);
+    }
+
+    #[test]
+    fn colored_shape_matches_build() {
+        let face = mononoki();
+        let g = glyph(&face, 'g');
+        let colored = GlyphShape::builder(16.0, Range::Px(4.0))
+            .color(&face, g)
+            .unwrap();
+
+        for px_per_em in [16.0, 32.0, 64.0] {
+            let builder = GlyphShape::builder(px_per_em, Range::Px(4.0));
+            let built = builder.build(&face, g).unwrap();
+            let framed = builder.build_colored(&colored).unwrap();
+            assert_eq!((framed.width, framed.height), (built.width, built.height));
+            assert_eq!(
+                framed.generate(),
+                built.generate(),
+                "{} px per em",
+                px_per_em
+            );
+        }
+    }
+
+    #[test]
+    fn dilation_increases_coverage() {
+        let face = mononoki();
+        let shape = GlyphShape::builder(32.0, Range::Px(4.0))
+            .build(&face, glyph(&face, 'l'))
+            .unwrap();
+
+        let thin = shape.generate().coverage();
+        let dilated = shape
+            .generate_with(&GenerateConfig {
+                dilation_px: 1.0,
+                ..Default::default()
+            })
+            .coverage();
+        assert!(dilated > thin, "{} is not above {}", dilated, thin);
+    }
+
+    #[test]
+    fn dilation_moves_the_outside_texel() {
+        let face = mononoki();
+        let shape = GlyphShape::builder(32.0, Range::Px(4.0))
+            .build(&face, glyph(&face, '.'))
+            .unwrap();
+        let config = GenerateConfig {
+            dilation_px: 1.0,
+            ..Default::default()
+        };
+
+        // a quarter of the range, so fully outside moves from 0 to 64
+        let outside = config.outside_texel(shape.effective_range_px());
+        assert_eq!(outside, u32::from_ne_bytes([64; 4]));
+
+        let mut bitmap = Bitmap::<Rgba<f32>>::new(shape.width, shape.height);
+        for pixel in bitmap.pixels_mut().iter_mut() {
+            pixel.r = -2.0;
+            pixel.g = -2.0;
+            pixel.b = -2.0;
+            pixel.a = -2.0;
+        }
+        let quantized = shape.quantize(&bitmap, &config);
+        assert!(quantized.data.iter().all(|texel| *texel == outside));
+    }
+
+    #[test]
+    fn invalid_dilation() {
+        for dilation_px in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let config = GenerateConfig {
+                dilation_px,
+                ..Default::default()
+            };
+
+            assert!(
+                matches!(
+                    config.validate(),
+                    Err(FontError::InvalidParameter {
+                        name: "dilation_px",
+                        ..
+                    })
+                ),
+                "dilation {} was accepted",
+                dilation_px
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_distance_scale() {
+        for distance_scale in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            let config = GenerateConfig {
+                distance_scale,
+                ..Default::default()
+            };
+
+            assert!(
+                matches!(
+                    config.validate(),
+                    Err(FontError::InvalidParameter {
+                        name: "distance_scale",
+                        ..
+                    })
+                ),
+                "distance scale {} was accepted",
+                distance_scale
+            );
+        }
+
+        assert!(GenerateConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn bias_distance_skips_padding() {
+        let mut bitmap = GlyphBitmap::with_row_alignment(3, 2, 256);
+        bitmap.bias_distance(0.5);
+
+        let biased = u32::from_ne_bytes([128; 4]);
+        assert!(bitmap.texel_rows().flatten().all(|texel| *texel == biased));
+        for row in bitmap.data.chunks(bitmap.stride as usize) {
+            assert!(row[3..].iter().all(|texel| *texel == GlyphBitmap::OUTSIDE));
+        }
+
+        let mut color = GlyphBitmap::new(2, 2);
+        color.kind = GlyphKind::Color;
+        color.bias_distance(0.5);
+        assert_eq!(color.data, GlyphBitmap::new(2, 2).data);
+    }
+
+    #[test]
+    fn autoframing_keeps_the_builder_scale() {
+        let face = mononoki();
+        let px_per_unit = 32.0 / face.units_per_em() as f64;
+        for c in ['.', 'A', 'g'] {
+            let shape = GlyphShape::builder(32.0, Range::Px(8.0))
+                .build(&face, glyph(&face, c))
+                .unwrap();
+            assert_eq!(shape.framing.scale.x, px_per_unit, "{:?}", c);
+            assert_eq!(shape.framing.scale.y, px_per_unit, "{:?}", c);
+            assert!((shape.effective_range_px() - 8.0).abs() < 1e-9, "{:?}", c);
+        }
+    }
+
+    #[test]
+    fn target_screen_px_range() {
+        let builder = GlyphShape::builder(64.0, Range::Px(1.0))
+            .target_screen_px_range(2.0, 16.0)
+            .unwrap();
+        assert!(matches!(builder.range, Range::Px(px) if px == 8.0));
+
+        let scaled = GlyphShape::builder(64.0, Range::Px(1.0))
+            .scaled_screen_px_range(2.0, 16.0, 0.5)
+            .unwrap();
+        assert!(matches!(scaled.range, Range::Px(px) if px == 4.0));
+
+        let cases = [
+            (0.0, 16.0, "screen_px_range"),
+            (2.0, 0.0, "min_screen_px_per_em"),
+            (2.0, f64::INFINITY, "min_screen_px_per_em"),
+            (f64::NAN, 16.0, "screen_px_range"),
+        ];
+        for (screen_px_range, min_screen_px_per_em, expected) in cases {
+            let result = GlyphShape::builder(64.0, Range::Px(1.0))
+                .target_screen_px_range(screen_px_range, min_screen_px_per_em);
+            assert!(
+                matches!(result, Err(FontError::InvalidParameter { name, .. }) if name == expected),
+                "{} {} was not rejected as an invalid {}",
+                screen_px_range,
+                min_screen_px_per_em,
+                expected
+            );
         }
     }
 }
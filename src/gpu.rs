@@ -0,0 +1,82 @@
+// Copyright (c) 2023 Marceline Cramer
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::glyph_atlas::GlyphAtlas;
+
+/// one glyph's metadata, laid out for a GPU storage buffer.
+///
+/// the layout is 48 tightly-packed bytes, matching this WGSL struct in both storage and uniform buffers:
+///
+/// ```wgsl
+/// struct Glyph {
+///     tex_rect: vec4<f32>,
+///     plane_rect: vec4<f32>,
+///     advance: f32,
+/// }
+/// ```
+///
+/// rects are `[min_x, min_y, max_x, max_y]`. `tex_rect` is in the same space as
+/// [crate::glyph_atlas::GlyphVertex::tex_coords], and `plane_rect` and `advance` are in ems
+/// relative to the pen position on the baseline.
+/// glyphs that are not in the atlas have zeroed rects, but still carry their advance.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct GpuGlyph {
+    pub tex_rect: [f32; 4],
+    pub plane_rect: [f32; 4],
+    pub advance: f32,
+    _padding: [f32; 3],
+}
+
+impl GlyphAtlas {
+    /// the metadata of every glyph in the face, indexed by glyph id.
+    pub fn gpu_glyphs(&self) -> Vec<GpuGlyph> {
+        self.advances
+            .iter()
+            .enumerate()
+            .map(|(index, advance)| {
+                let mut record = GpuGlyph {
+                    advance: *advance,
+                    ..Default::default()
+                };
+
+                if let Some(Some(info)) = self.glyphs.get(index) {
+                    let [min, _, _, max] = info.vertices;
+                    record.tex_rect = [
+                        min.tex_coords.x,
+                        min.tex_coords.y,
+                        max.tex_coords.x,
+                        max.tex_coords.y,
+                    ];
+                    record.plane_rect = [
+                        min.position.x,
+                        min.position.y,
+                        max.position.x,
+                        max.position.y,
+                    ];
+                }
+
+                record
+            })
+            .collect()
+    }
+
+    /// [Self::gpu_glyphs] as bytes, ready to be written to a buffer.
+    pub fn gpu_buffer(&self) -> Vec<u8> {
+        bytemuck::cast_slice(&self.gpu_glyphs()).to_vec()
+    }
+}
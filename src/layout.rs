@@ -0,0 +1,324 @@
+// Copyright (c) 2023 Marceline Cramer
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{hash_map::Entry, HashMap};
+
+use glam::Vec2;
+use msdfgen::Range;
+use ttf_parser::{kern, Face, GlyphId};
+
+use crate::{
+    error::{FontError, FontResult},
+    glyph_bitmap::{GlyphBitmap, GlyphShapeBuilder},
+    metrics::{FontMetrics, MetricSource},
+};
+
+/// a glyph positioned on a line of text.
+#[derive(Copy, Clone, Debug)]
+pub struct PlacedGlyph {
+    pub glyph: GlyphId,
+    /// the pen position on the baseline, in pixels.
+    pub position: Vec2,
+}
+
+/// options for the char-based layout and measurement functions.
+#[derive(Copy, Clone, Debug)]
+pub struct LayoutOptions {
+    /// skips control characters and zero-width formatting characters (like BOMs and joiners)
+    /// instead of laying them out, which usually shows them as .notdef boxes.
+    /// line feeds and carriage returns never place a glyph, regardless of this option.
+    pub skip_invisible: bool,
+
+    /// the width of a tab, as a multiple of the face's space advance.
+    /// tabs never place a glyph, regardless of [Self::skip_invisible].
+    pub tab_width: f32,
+}
+
+impl Default for LayoutOptions {
+    fn default() -> Self {
+        Self {
+            skip_invisible: true,
+            tab_width: 4.0,
+        }
+    }
+}
+
+/// returns true for the characters that [LayoutOptions::skip_invisible] skips.
+pub fn is_invisible(c: char) -> bool {
+    c.is_control()
+        || matches!(
+            c,
+            '\u{00AD}'
+                | '\u{200B}'..='\u{200F}'
+                | '\u{202A}'..='\u{202E}'
+                | '\u{2060}'..='\u{2064}'
+                | '\u{FEFF}'
+        )
+}
+
+/// lays out a single line of text, applying advances and kerning.
+/// characters missing from the face are placed as the .notdef glyph.
+pub fn layout_line(face: &Face, px_per_em: f32, text: &str) -> Vec<PlacedGlyph> {
+    layout_line_with(face, px_per_em, text, &LayoutOptions::default())
+}
+
+/// like [layout_line], with explicit options.
+pub fn layout_line_with(
+    face: &Face,
+    px_per_em: f32,
+    text: &str,
+    options: &LayoutOptions,
+) -> Vec<PlacedGlyph> {
+    let mut placed = Vec::with_capacity(text.len());
+    walk_line(face, px_per_em, text, options, |glyph, x| {
+        placed.push(PlacedGlyph {
+            glyph,
+            position: Vec2::new(x, 0.0),
+        });
+    });
+    placed
+}
+
+/// measures the width in pixels of a single line of text.
+/// this gives the same result as [layout_line] without allocating, and never outlines a glyph.
+pub fn measure_line(face: &Face, px_per_em: f32, text: &str) -> f32 {
+    measure_line_with(face, px_per_em, text, &LayoutOptions::default())
+}
+
+/// like [measure_line], with explicit options.
+pub fn measure_line_with(face: &Face, px_per_em: f32, text: &str, options: &LayoutOptions) -> f32 {
+    walk_line(face, px_per_em, text, options, |_, _| {})
+}
+
+/// measures the size in pixels of a paragraph, wrapping words to `wrap_width`.
+/// newlines always start a new line. a single word wider than `wrap_width` is not broken.
+pub fn measure_paragraph(face: &Face, px_per_em: f32, text: &str, wrap_width: f32) -> Vec2 {
+    measure_paragraph_with(face, px_per_em, text, wrap_width, &LayoutOptions::default())
+}
+
+/// like [measure_paragraph], with explicit options.
+pub fn measure_paragraph_with(
+    face: &Face,
+    px_per_em: f32,
+    text: &str,
+    wrap_width: f32,
+    options: &LayoutOptions,
+) -> Vec2 {
+    let measure_line = |text: &str| measure_line_with(face, px_per_em, text, options);
+    let mut width: f32 = 0.0;
+    let mut lines = 0;
+
+    for line in text.split('\n') {
+        // byte ranges into `line`: the wrapped line so far, and the end of its last word
+        let mut start = 0;
+        let mut end = 0;
+        let mut offset = 0;
+
+        for word in line.split(' ') {
+            let word_start = offset;
+            let word_end = offset + word.len();
+            offset = word_end + 1;
+
+            if word.is_empty() {
+                continue;
+            }
+
+            if end > start && measure_line(&line[start..word_end]) > wrap_width {
+                width = width.max(measure_line(&line[start..end]));
+                lines += 1;
+                start = word_start;
+            }
+
+            end = word_end;
+        }
+
+        width = width.max(measure_line(&line[start..end]));
+        lines += 1;
+    }
+
+    Vec2::new(width, lines as f32 * line_height(face, px_per_em))
+}
+
+/// lays out a single line of text and renders it into a standalone MTSDF bitmap sized to fit its ink.
+///
+/// this is a convenience for thumbnails and tests, not a replacement for an atlas.
+/// the bitmap's first row is below the lowest descender, like any glyph bitmap, so nothing is clipped.
+/// overlapping tiles are combined with the maximum of each channel, which keeps neighboring glyphs intact.
+/// glyphs without outlines, like spaces, only advance the pen. text without any ink renders an empty bitmap.
+pub fn render_string_to_bitmap(
+    face: &Face,
+    px_per_em: f64,
+    range: Range<f64>,
+    text: &str,
+) -> FontResult<GlyphBitmap> {
+    let builder = GlyphShapeBuilder::new(px_per_em, range);
+    let mut generated: HashMap<GlyphId, Option<(Vec2, GlyphBitmap)>> = HashMap::new();
+    let mut tiles = vec![];
+    let mut min = Vec2::splat(f32::INFINITY);
+    let mut max = Vec2::splat(f32::NEG_INFINITY);
+    for placed in layout_line(face, px_per_em as f32, text) {
+        let tile = match generated.entry(placed.glyph) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(match builder.build(face, placed.glyph) {
+                Ok(shape) => Some((shape.anchor * px_per_em as f32, shape.generate())),
+                Err(FontError::GlyphShape(_)) => None,
+                Err(err) => return Err(err),
+            }),
+        };
+
+        if let Some((origin, tile)) = tile {
+            let corner = (placed.position - *origin).round();
+            min = min.min(corner);
+            max = max.max(corner + Vec2::new(tile.width as f32, tile.height as f32));
+            tiles.push((placed.glyph, corner));
+        }
+    }
+
+    if tiles.is_empty() {
+        return Ok(GlyphBitmap::new(0, 0));
+    }
+
+    let size = max - min;
+    let mut bitmap = GlyphBitmap::new(size.x as u32, size.y as u32);
+    for (glyph, corner) in tiles {
+        let (_, tile) = generated[&glyph].as_ref().unwrap();
+        let offset = corner - min;
+        union_into(tile, &mut bitmap, offset.x as u32, offset.y as u32);
+    }
+
+    Ok(bitmap)
+}
+
+/// combines `src` into `dst` at `(x, y)` by taking the maximum of each channel.
+fn union_into(src: &GlyphBitmap, dst: &mut GlyphBitmap, x: u32, y: u32) {
+    let (src_stride, dst_stride) = (src.row_stride() / 4, dst.row_stride() / 4);
+    for row in 0..src.height as usize {
+        let src_start = row * src_stride;
+        let dst_start = (row + y as usize) * dst_stride + x as usize;
+        let src_row = &src.data[src_start..src_start + src.width as usize];
+        let dst_row = &mut dst.data[dst_start..dst_start + src.width as usize];
+        for (dst_texel, src_texel) in dst_row.iter_mut().zip(src_row) {
+            let (a, b) = (dst_texel.to_ne_bytes(), src_texel.to_ne_bytes());
+            *dst_texel = u32::from_ne_bytes([0, 1, 2, 3].map(|i| a[i].max(b[i])));
+        }
+    }
+}
+
+/// the horizontal advance of a glyph in pixels.
+/// this only reads the face's metrics, so it is far cheaper than building a [crate::glyph_bitmap::GlyphShape].
+pub fn advance_px(face: &Face, px_per_em: f32, glyph: GlyphId) -> f32 {
+    let advance = face.glyph_hor_advance(glyph).unwrap_or(0);
+    advance as f32 * px_per_em / face.units_per_em() as f32
+}
+
+/// the horizontal kerning adjustment between two glyphs in pixels.
+pub(crate) fn kerning(face: &Face, px_per_em: f32, left: GlyphId, right: GlyphId) -> f32 {
+    let Some(kern) = face.tables().kern else {
+        return 0.0;
+    };
+
+    let kerning = kern
+        .subtables
+        .into_iter()
+        .filter(is_kerning_subtable)
+        .find_map(|subtable| subtable.glyphs_kerning(left, right))
+        .unwrap_or(0);
+
+    kerning as f32 * px_per_em / face.units_per_em() as f32
+}
+
+/// returns true for the `kern` subtables that adjust horizontal advances.
+/// cross-stream subtables move glyphs perpendicular to the line instead, so they are skipped.
+pub(crate) fn is_kerning_subtable(subtable: &kern::Subtable) -> bool {
+    subtable.horizontal && !subtable.variable && !subtable.has_cross_stream
+}
+
+/// the distance between consecutive baselines in pixels.
+pub(crate) fn line_height(face: &Face, px_per_em: f32) -> f32 {
+    FontMetrics::new(face).line_height(px_per_em, MetricSource::Face)
+}
+
+/// calls `f` with each glyph of the line and its pen position, then returns the line's width.
+fn walk_line(
+    face: &Face,
+    px_per_em: f32,
+    text: &str,
+    options: &LayoutOptions,
+    mut f: impl FnMut(GlyphId, f32),
+) -> f32 {
+    let mut x = 0.0;
+    let mut last = None;
+
+    for c in text.chars() {
+        if c == '\t' {
+            let space = face.glyph_index(' ').unwrap_or(GlyphId(0));
+            x += options.tab_width * advance_px(face, px_per_em, space);
+            last = None;
+            continue;
+        }
+
+        if c == '\n' || c == '\r' {
+            last = None;
+            continue;
+        }
+
+        if options.skip_invisible && is_invisible(c) {
+            continue;
+        }
+
+        let glyph = face.glyph_index(c).unwrap_or(GlyphId(0));
+
+        if let Some(last) = last {
+            x += kerning(face, px_per_em, last, glyph);
+        }
+
+        f(glyph, x);
+        x += advance_px(face, px_per_em, glyph);
+        last = Some(glyph);
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skip_invisible_characters() {
+        let face = Face::parse(include_bytes!("../examples/mononoki-Regular.ttf"), 0).unwrap();
+        let text = "\u{FEFF}A\u{200D}B\r\nC";
+        let glyphs = |options: &LayoutOptions| -> Vec<GlyphId> {
+            layout_line_with(&face, 16.0, text, options)
+                .iter()
+                .map(|placed| placed.glyph)
+                .collect()
+        };
+
+        let visible: Vec<_> = "ABC"
+            .chars()
+            .map(|c| face.glyph_index(c).unwrap())
+            .collect();
+        assert_eq!(glyphs(&LayoutOptions::default()), visible);
+
+        let options = LayoutOptions {
+            skip_invisible: false,
+            ..Default::default()
+        };
+        let placed = glyphs(&options);
+        assert_eq!(placed.len(), 5);
+        assert_eq!([placed[1], placed[3], placed[4]], visible[..]);
+    }
+}
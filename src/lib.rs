@@ -13,6 +13,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod bmfont;
+pub mod dynamic_atlas;
 pub mod error;
+pub mod font;
 pub mod glyph_atlas;
 pub mod glyph_bitmap;
+pub mod gpu;
+pub mod layout;
+pub mod metrics;
+mod msdf_atlas_gen;
+mod outline;
+pub mod prelude;
+pub mod unicode;
+pub mod variations;
@@ -0,0 +1,130 @@
+// Copyright (c) 2023 Marceline Cramer
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ttf_parser::Face;
+
+/// which of a face's vertical metrics to space lines with.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum MetricSource {
+    /// the metrics the face asks for: `OS/2` typographic metrics if its `USE_TYPO_METRICS` flag is set,
+    /// and `hhea` metrics otherwise. this is what [FontMetrics::ascender] and friends hold.
+    #[default]
+    Face,
+
+    /// the `hhea` metrics, which macOS and most browsers use.
+    Hhea,
+
+    /// the `OS/2` typographic metrics, falling back to `hhea` if the face has no `OS/2` table.
+    Typographic,
+}
+
+/// one set of a face's vertical line metrics, in font units.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LineMetrics {
+    pub ascender: i16,
+    pub descender: i16,
+    pub line_gap: i16,
+}
+
+/// the vertical metrics of a face, in font units.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FontMetrics {
+    pub units_per_em: u16,
+    pub ascender: i16,
+    pub descender: i16,
+    pub line_gap: i16,
+    pub underline_position: i16,
+    pub underline_thickness: i16,
+    pub hhea: LineMetrics,
+    pub typographic: Option<LineMetrics>,
+}
+
+impl FontMetrics {
+    pub fn new(face: &Face) -> Self {
+        let underline = face.underline_metrics();
+        let typographic = match (
+            face.typographic_ascender(),
+            face.typographic_descender(),
+            face.typographic_line_gap(),
+        ) {
+            (Some(ascender), Some(descender), Some(line_gap)) => Some(LineMetrics {
+                ascender,
+                descender,
+                line_gap,
+            }),
+            _ => None,
+        };
+
+        Self {
+            units_per_em: face.units_per_em(),
+            ascender: face.ascender(),
+            descender: face.descender(),
+            line_gap: face.line_gap(),
+            underline_position: underline.map(|u| u.position).unwrap_or(0),
+            underline_thickness: underline.map(|u| u.thickness).unwrap_or(0),
+            hhea: LineMetrics {
+                ascender: face.tables().hhea.ascender,
+                descender: face.tables().hhea.descender,
+                line_gap: face.tables().hhea.line_gap,
+            },
+            typographic,
+        }
+    }
+
+    /// the line metrics from the given source.
+    pub fn line_metrics(&self, source: MetricSource) -> LineMetrics {
+        match source {
+            MetricSource::Face => LineMetrics {
+                ascender: self.ascender,
+                descender: self.descender,
+                line_gap: self.line_gap,
+            },
+            MetricSource::Hhea => self.hhea,
+            MetricSource::Typographic => self.typographic.unwrap_or(self.hhea),
+        }
+    }
+
+    /// how far to move the pen down between lines, in pixels.
+    /// this is `ascender - descender + line_gap` from the given source, scaled to the given size.
+    pub fn line_height(&self, px_per_em: f32, source: MetricSource) -> f32 {
+        let metrics = self.line_metrics(source);
+        let units = metrics.ascender as f32 - metrics.descender as f32 + metrics.line_gap as f32;
+        units * self.px_per_unit(px_per_em)
+    }
+
+    /// the number of pixels per font unit at the given size.
+    pub fn px_per_unit(&self, px_per_em: f32) -> f32 {
+        px_per_em / self.units_per_em as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mononoki_line_height() {
+        let face = Face::parse(include_bytes!("../examples/mononoki-Regular.ttf"), 0).unwrap();
+        let metrics = FontMetrics::new(&face);
+
+        // mononoki doesn't set USE_TYPO_METRICS, so the face's metrics are its hhea metrics
+        assert_eq!(metrics.line_height(16.0, MetricSource::Face), 17.96875);
+        assert_eq!(metrics.line_height(16.0, MetricSource::Hhea), 17.96875);
+        assert_eq!(
+            metrics.line_height(16.0, MetricSource::Typographic),
+            19.203125
+        );
+    }
+}
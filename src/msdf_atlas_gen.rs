@@ -0,0 +1,176 @@
+// Copyright (c) 2023 Marceline Cramer
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::{self, Write};
+
+use crate::{glyph_atlas::GlyphAtlas, glyph_bitmap::GlyphMode, metrics::MetricSource};
+
+impl GlyphAtlas {
+    /// writes a JSON layout matching the one produced by Chlumsky's msdf-atlas-gen.
+    ///
+    /// plane bounds and metrics are in ems, atlas bounds are in texels,
+    /// and `yOrigin` is `bottom` because atlas rows are stored bottom-up.
+    pub fn write_msdf_atlas_json<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let kind = match self.config.mode {
+            GlyphMode::Mtsdf => "mtsdf",
+            GlyphMode::Sdf => "sdf",
+        };
+
+        let units_per_em = self.metrics.units_per_em as f32;
+        let em = |units: i16| units as f32 / units_per_em;
+        let line_height = self.metrics.line_height(1.0, MetricSource::Face);
+
+        write!(
+            writer,
+            "{{\"atlas\":{{\"type\":\"{}\",\"distanceRange\":{},\"distanceRangeMiddle\":0,\"size\":{},\"width\":{},\"height\":{},\"yOrigin\":\"bottom\"}},",
+            kind,
+            self.range_px(),
+            self.px_per_em,
+            self.width,
+            self.height,
+        )?;
+
+        write!(
+            writer,
+            "\"metrics\":{{\"emSize\":1,\"lineHeight\":{},\"ascender\":{},\"descender\":{},\"underlineY\":{},\"underlineThickness\":{}}},",
+            line_height,
+            em(self.metrics.ascender),
+            em(self.metrics.descender),
+            em(self.metrics.underline_position),
+            em(self.metrics.underline_thickness),
+        )?;
+
+        write!(writer, "\"glyphs\":[")?;
+        for (index, (c, glyph)) in self.chars.iter().enumerate() {
+            if index > 0 {
+                write!(writer, ",")?;
+            }
+
+            write!(
+                writer,
+                "{{\"unicode\":{},\"advance\":{}",
+                *c as u32, self.advances[glyph.0 as usize]
+            )?;
+
+            if let Some(info) = &self.glyphs[glyph.0 as usize] {
+                let px_per_em = self.px_per_em as f32;
+                let left = -info.shape.anchor.x;
+                let bottom = -info.shape.anchor.y;
+                let right = left + info.size.x as f32 / px_per_em;
+                let top = bottom + info.size.y as f32 / px_per_em;
+
+                write!(
+                    writer,
+                    ",\"planeBounds\":{{\"left\":{},\"bottom\":{},\"right\":{},\"top\":{}}}",
+                    left, bottom, right, top
+                )?;
+
+                write!(
+                    writer,
+                    ",\"atlasBounds\":{{\"left\":{},\"bottom\":{},\"right\":{},\"top\":{}}}",
+                    info.position.x,
+                    info.position.y,
+                    info.position.x + info.size.x,
+                    info.position.y + info.size.y,
+                )?;
+            }
+
+            write!(writer, "}}")?;
+        }
+        write!(writer, "],")?;
+
+        let chars_by_glyph = self.chars_by_glyph();
+
+        write!(writer, "\"kerning\":[")?;
+        let mut first = true;
+        for (left, right, advance) in self.kerning.iter() {
+            let (Some(lefts), Some(rights)) = (chars_by_glyph.get(left), chars_by_glyph.get(right))
+            else {
+                continue;
+            };
+
+            for unicode1 in lefts.iter() {
+                for unicode2 in rights.iter() {
+                    if !first {
+                        write!(writer, ",")?;
+                    }
+
+                    first = false;
+                    write!(
+                        writer,
+                        "{{\"unicode1\":{},\"unicode2\":{},\"advance\":{}}}",
+                        *unicode1 as u32, *unicode2 as u32, advance
+                    )?;
+                }
+            }
+        }
+        write!(writer, "]}}")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ttf_parser::Face;
+
+    use super::*;
+
+    #[test]
+    fn msdf_atlas_gen_keys() {
+        let face = Face::parse(include_bytes!("../examples/mononoki-Regular.ttf"), 0).unwrap();
+        let (mut atlas, _) = GlyphAtlas::builder()
+            .build_chars(&face, "AV".chars())
+            .unwrap();
+
+        // mononoki has no kern table, so add a pair to check the kerning layout
+        let (a, v) = (atlas.chars[&'A'], atlas.chars[&'V']);
+        atlas.kerning.push((a, v, -0.0625));
+
+        let mut json = Vec::new();
+        atlas.write_msdf_atlas_json(&mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+
+        assert!(json.starts_with("{\"atlas\":{\"type\":\"mtsdf\",\"distanceRange\":"));
+        for key in [
+            "\"distanceRangeMiddle\":",
+            "\"size\":",
+            "\"width\":",
+            "\"height\":",
+            "\"yOrigin\":\"bottom\"",
+            "\"metrics\":{\"emSize\":1,\"lineHeight\":",
+            "\"ascender\":",
+            "\"descender\":",
+            "\"underlineY\":",
+            "\"underlineThickness\":",
+            "\"glyphs\":[{\"unicode\":65,\"advance\":",
+            "{\"unicode\":86,\"advance\":",
+            "\"kerning\":[{\"unicode1\":65,\"unicode2\":86,\"advance\":-0.0625}]",
+        ] {
+            assert!(json.contains(key), "missing {} in {}", key, json);
+        }
+
+        for bounds in ["\"planeBounds\":{", "\"atlasBounds\":{"] {
+            assert_eq!(json.matches(bounds).count(), 2, "{} in {}", bounds, json);
+        }
+
+        for side in ["left", "bottom", "right", "top"] {
+            let key = format!("\"{}\":", side);
+            assert_eq!(json.matches(&key).count(), 4, "{} in {}", key, json);
+        }
+
+        assert!(json.ends_with("]}"));
+    }
+}
@@ -0,0 +1,236 @@
+// Copyright (c) 2023 Marceline Cramer
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use msdfgen::{Contour, EdgeColor, EdgeHolder, Shape, Vector2};
+use ttf_parser::{Face, GlyphId, OutlineBuilder};
+
+#[derive(Copy, Clone, Debug)]
+enum Segment {
+    Line(Vector2<f64>),
+    Quad(Vector2<f64>, Vector2<f64>),
+    Curve(Vector2<f64>, Vector2<f64>, Vector2<f64>),
+}
+
+impl Segment {
+    fn end(&self) -> Vector2<f64> {
+        match *self {
+            Segment::Line(p) => p,
+            Segment::Quad(_, p) => p,
+            Segment::Curve(_, _, p) => p,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct RecordedContour {
+    start: Vector2<f64>,
+    segments: Vec<Segment>,
+}
+
+impl RecordedContour {
+    /// the shoelace area of the contour's control polygon. positive means counter-clockwise.
+    /// this is only an approximation for curves, but it is always good enough for orientation.
+    fn signed_area(&self) -> f64 {
+        let mut points = vec![self.start];
+        for segment in self.segments.iter() {
+            match *segment {
+                Segment::Line(p) => points.push(p),
+                Segment::Quad(c, p) => points.extend([c, p]),
+                Segment::Curve(c1, c2, p) => points.extend([c1, c2, p]),
+            }
+        }
+
+        let mut area = 0.0;
+        for (a, b) in points.iter().zip(points.iter().cycle().skip(1)) {
+            area += a.x * b.y - b.x * a.y;
+        }
+
+        area / 2.0
+    }
+
+    fn reversed(&self) -> Self {
+        let mut segments = Vec::with_capacity(self.segments.len());
+        let mut start = self.start;
+        let mut starts = Vec::with_capacity(self.segments.len());
+        for segment in self.segments.iter() {
+            starts.push(start);
+            start = segment.end();
+        }
+
+        for (segment, start) in self.segments.iter().zip(starts).rev() {
+            segments.push(match *segment {
+                Segment::Line(_) => Segment::Line(start),
+                Segment::Quad(c, _) => Segment::Quad(c, start),
+                Segment::Curve(c1, c2, _) => Segment::Curve(c2, c1, start),
+            });
+        }
+
+        Self {
+            start: self.segments.last().map(Segment::end).unwrap_or(self.start),
+            segments,
+        }
+    }
+}
+
+/// records a glyph outline so that it can be inspected and turned into a [Shape].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct OutlineRecorder {
+    contours: Vec<RecordedContour>,
+}
+
+impl OutlineBuilder for OutlineRecorder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.contours.push(RecordedContour {
+            start: Vector2::new(x as f64, y as f64),
+            segments: vec![],
+        });
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.push(Segment::Line(Vector2::new(x as f64, y as f64)));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.push(Segment::Quad(
+            Vector2::new(x1 as f64, y1 as f64),
+            Vector2::new(x as f64, y as f64),
+        ));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.push(Segment::Curve(
+            Vector2::new(x1 as f64, y1 as f64),
+            Vector2::new(x2 as f64, y2 as f64),
+            Vector2::new(x as f64, y as f64),
+        ));
+    }
+
+    fn close(&mut self) {}
+}
+
+impl OutlineRecorder {
+    fn push(&mut self, segment: Segment) {
+        if let Some(contour) = self.contours.last_mut() {
+            contour.segments.push(segment);
+        }
+    }
+
+    /// outlines the classic missing-glyph box, spanning the .notdef glyph's advance width.
+    pub fn notdef_box(face: &Face, outer_clockwise: bool) -> Self {
+        let units_per_em = face.units_per_em() as f32;
+        let advance = face
+            .glyph_hor_advance(GlyphId(0))
+            .filter(|advance| *advance > 0)
+            .map(|advance| advance as f32)
+            .unwrap_or(units_per_em / 2.0);
+
+        let height = face.capital_height().filter(|height| *height > 0);
+        let height = height
+            .map(|height| height as f32)
+            .unwrap_or(units_per_em * 0.7);
+        let margin = units_per_em * 0.05;
+        let stroke = units_per_em * 0.05;
+
+        let mut recorder = Self::default();
+        let outer = (margin, 0.0, advance - margin, height);
+        let inner = (outer.0 + stroke, stroke, outer.2 - stroke, height - stroke);
+        recorder.rectangle(outer, outer_clockwise);
+        recorder.rectangle(inner, !outer_clockwise);
+        recorder
+    }
+
+    fn rectangle(&mut self, (left, bottom, right, top): (f32, f32, f32, f32), clockwise: bool) {
+        self.move_to(left, bottom);
+        if clockwise {
+            self.line_to(left, top);
+            self.line_to(right, top);
+            self.line_to(right, bottom);
+        } else {
+            self.line_to(right, bottom);
+            self.line_to(right, top);
+            self.line_to(left, top);
+        }
+        self.close();
+    }
+
+    /// returns true if the outermost contour is wound against the given convention.
+    /// TrueType outlines wind outer contours clockwise and CFF outlines wind them counter-clockwise.
+    pub fn has_suspect_winding(&self, outer_clockwise: bool) -> bool {
+        let outer = self
+            .contours
+            .iter()
+            .map(RecordedContour::signed_area)
+            .max_by(|a, b| a.abs().total_cmp(&b.abs()));
+
+        match outer {
+            Some(area) if area != 0.0 => (area < 0.0) != outer_clockwise,
+            _ => false,
+        }
+    }
+
+    /// reverses the direction of every contour.
+    pub fn reverse(&mut self) {
+        for contour in self.contours.iter_mut() {
+            *contour = contour.reversed();
+        }
+    }
+
+    /// builds an uncolored msdfgen shape from the recorded outline.
+    pub fn to_shape(&self) -> Shape {
+        let mut shape = Shape::default();
+        for recorded in self.contours.iter() {
+            let mut contour = Contour::default();
+            let mut start = recorded.start;
+            for segment in recorded.segments.iter() {
+                let color = EdgeColor::default();
+                let edge = match *segment {
+                    Segment::Line(p) => EdgeHolder::new_linear(start, p, color),
+                    Segment::Quad(c, p) => EdgeHolder::new_quadratic(start, c, p, color),
+                    Segment::Curve(c1, c2, p) => EdgeHolder::new_cubic(start, c1, c2, p, color),
+                };
+
+                contour.add_edge(&edge);
+                start = segment.end();
+            }
+
+            // implicitly close the contour like ttf-parser does
+            if start.x != recorded.start.x || start.y != recorded.start.y {
+                let edge = EdgeHolder::new_linear(start, recorded.start, EdgeColor::default());
+                contour.add_edge(&edge);
+            }
+
+            shape.add_contour(&contour);
+        }
+
+        shape
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reverse_fixes_suspect_winding() {
+        let mut recorder = OutlineRecorder::default();
+        recorder.rectangle((0.0, 0.0, 100.0, 100.0), false);
+        assert!(recorder.has_suspect_winding(true));
+        assert!(!recorder.has_suspect_winding(false));
+
+        recorder.reverse();
+        assert!(!recorder.has_suspect_winding(true));
+        assert!(recorder.has_suspect_winding(false));
+    }
+}
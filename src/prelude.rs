@@ -0,0 +1,37 @@
+// Copyright (c) 2023 Marceline Cramer
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! the types most callers need, in one import.
+//!
+//! `use font_mud::prelude::*;` brings in the crate's public types, along with the
+//! [Face], [GlyphId], [Range], and [Vec2] types of the crate versions font-mud is built against.
+
+pub use glam::Vec2;
+pub use msdfgen::Range;
+pub use ttf_parser::{Face, GlyphId};
+
+pub use crate::dynamic_atlas::{DynamicAtlas, DynamicPage, GlyphRect};
+pub use crate::error::{FontError, FontResult, GlyphShapeError};
+pub use crate::font::Font;
+pub use crate::glyph_atlas::{
+    GlyphAtlas, GlyphAtlasBuilder, GlyphInfo, GlyphVertex, GridLayout, PackOrder,
+};
+pub use crate::glyph_bitmap::{
+    ChannelOrder, ColoredShape, EdgeColoring, GenerateConfig, GlyphBitmap, GlyphBitmapView,
+    GlyphKind, GlyphMode, GlyphShape, GlyphShapeBuilder, Rounding, ScratchBitmap, Winding,
+};
+pub use crate::gpu::GpuGlyph;
+pub use crate::layout::{LayoutOptions, PlacedGlyph};
+pub use crate::metrics::{FontMetrics, LineMetrics, MetricSource};
@@ -0,0 +1,72 @@
+// Copyright (c) 2023 Marceline Cramer
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::RangeInclusive;
+
+/// a curated set of common Unicode blocks, by their official names.
+pub const BLOCKS: &[(&str, RangeInclusive<char>)] = &[
+    ("Basic Latin", '\u{0000}'..='\u{007F}'),
+    ("Latin-1 Supplement", '\u{0080}'..='\u{00FF}'),
+    ("Latin Extended-A", '\u{0100}'..='\u{017F}'),
+    ("Latin Extended-B", '\u{0180}'..='\u{024F}'),
+    ("IPA Extensions", '\u{0250}'..='\u{02AF}'),
+    ("Greek and Coptic", '\u{0370}'..='\u{03FF}'),
+    ("Cyrillic", '\u{0400}'..='\u{04FF}'),
+    ("Cyrillic Supplement", '\u{0500}'..='\u{052F}'),
+    ("Armenian", '\u{0530}'..='\u{058F}'),
+    ("Hebrew", '\u{0590}'..='\u{05FF}'),
+    ("Arabic", '\u{0600}'..='\u{06FF}'),
+    ("Devanagari", '\u{0900}'..='\u{097F}'),
+    ("Thai", '\u{0E00}'..='\u{0E7F}'),
+    ("Hangul Jamo", '\u{1100}'..='\u{11FF}'),
+    ("Latin Extended Additional", '\u{1E00}'..='\u{1EFF}'),
+    ("General Punctuation", '\u{2000}'..='\u{206F}'),
+    ("Currency Symbols", '\u{20A0}'..='\u{20CF}'),
+    ("Letterlike Symbols", '\u{2100}'..='\u{214F}'),
+    ("Arrows", '\u{2190}'..='\u{21FF}'),
+    ("Mathematical Operators", '\u{2200}'..='\u{22FF}'),
+    ("Box Drawing", '\u{2500}'..='\u{257F}'),
+    ("Block Elements", '\u{2580}'..='\u{259F}'),
+    ("Geometric Shapes", '\u{25A0}'..='\u{25FF}'),
+    ("Miscellaneous Symbols", '\u{2600}'..='\u{26FF}'),
+    ("Dingbats", '\u{2700}'..='\u{27BF}'),
+    ("CJK Symbols and Punctuation", '\u{3000}'..='\u{303F}'),
+    ("Hiragana", '\u{3040}'..='\u{309F}'),
+    ("Katakana", '\u{30A0}'..='\u{30FF}'),
+    ("CJK Unified Ideographs", '\u{4E00}'..='\u{9FFF}'),
+    ("Hangul Syllables", '\u{AC00}'..='\u{D7AF}'),
+    ("Private Use Area", '\u{E000}'..='\u{F8FF}'),
+    ("Halfwidth and Fullwidth Forms", '\u{FF00}'..='\u{FFEF}'),
+];
+
+/// looks up one of [BLOCKS] by name, for passing to [crate::glyph_atlas::bake_char_range].
+///
+/// names match loosely, as Unicode recommends: case, spaces, hyphens, and underscores are ignored,
+/// so `"latin_1_supplement"` finds "Latin-1 Supplement". characters the face lacks are skipped when baking.
+/// blocks outside the curated set aren't known, but their raw ranges can be baked directly.
+pub fn block(name: &str) -> Option<RangeInclusive<char>> {
+    let loose = |name: &str| {
+        name.chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_'))
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+
+    let name = loose(name);
+    BLOCKS
+        .iter()
+        .find(|(block, _)| loose(block) == name)
+        .map(|(_, range)| range.clone())
+}
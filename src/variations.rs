@@ -0,0 +1,82 @@
+// Copyright (c) 2023 Marceline Cramer
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ttf_parser::{Face, Tag};
+
+/// the named instances of a variable face, like "Bold" or "Condensed", with each one's axis coordinates.
+///
+/// pass the coordinates to [Face::set_variation] to select an instance before outlining glyphs.
+/// instances are read from the `fvar` table, and named from the `name` table,
+/// falling back to `instance <index>` if the name is missing. faces without `fvar` have no instances.
+pub fn named_instances(face: &Face) -> Vec<(String, Vec<(Tag, f32)>)> {
+    let Some(fvar) = face.raw_face().table(Tag::from_bytes(b"fvar")) else {
+        return vec![];
+    };
+
+    let axes: Vec<Tag> = face
+        .variation_axes()
+        .into_iter()
+        .map(|axis| axis.tag)
+        .collect();
+    parse_instances(fvar, &axes)
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .map(|(index, (name_id, coordinates))| {
+            let name = face
+                .names()
+                .into_iter()
+                .filter(|name| name.name_id == name_id)
+                .find_map(|name| name.to_string())
+                .unwrap_or_else(|| format!("instance {}", index));
+
+            (name, axes.iter().copied().zip(coordinates).collect())
+        })
+        .collect()
+}
+
+/// reads each instance record's subfamily name id and coordinates, or `None` if the table is malformed.
+fn parse_instances(fvar: &[u8], axes: &[Tag]) -> Option<Vec<(u16, Vec<f32>)>> {
+    let u16_at = |offset: usize| {
+        Some(u16::from_be_bytes(
+            fvar.get(offset..offset + 2)?.try_into().ok()?,
+        ))
+    };
+    let fixed_at = |offset: usize| {
+        let bits = i32::from_be_bytes(fvar.get(offset..offset + 4)?.try_into().ok()?);
+        Some(bits as f32 / 65536.0)
+    };
+
+    let axes_offset = u16_at(4)? as usize;
+    let axis_count = u16_at(8)? as usize;
+    let axis_size = u16_at(10)? as usize;
+    let instance_count = u16_at(12)? as usize;
+    let instance_size = u16_at(14)? as usize;
+    if axis_count != axes.len() || instance_size < 4 + axis_count * 4 {
+        return None;
+    }
+
+    let instances_offset = axes_offset + axis_count * axis_size;
+    (0..instance_count)
+        .map(|index| {
+            let offset = instances_offset + index * instance_size;
+            let name_id = u16_at(offset)?;
+            let coordinates = (0..axis_count)
+                .map(|axis| fixed_at(offset + 4 + axis * 4))
+                .collect::<Option<_>>()?;
+            Some((name_id, coordinates))
+        })
+        .collect()
+}